@@ -0,0 +1,60 @@
+#![warn(clippy::all, clippy::pedantic)]
+use std::{
+  env,
+  fs,
+  hint::black_box,
+  time::{Duration, Instant},
+};
+use aoc_runner::{Day, Solution};
+use criterion::{BenchmarkId, Criterion, SamplingMode, Throughput};
+
+/// Scales used when a day does not ask for anything else.
+pub const DEFAULT_SCALES: &[usize] = &[10, 100, 1000];
+
+/// Benchmarks parsing, part one and part two of a day separately, against the
+/// day's own `input.txt` (when there is one) and against generated inputs of
+/// each scale, seeded with the day number. The scales can be overridden with a
+/// comma separated list in `AOC_BENCH_SCALES`, e.g.
+/// `AOC_BENCH_SCALES=10 cargo bench -p day-09`.
+///
+/// An input that takes more than a second to solve once is measured as few
+/// times as criterion allows: ten samples of one run each.
+///
+/// # Panics
+///
+/// Panics if `AOC_BENCH_SCALES` is not a list of numbers, or if an input
+/// cannot be parsed or solved.
+pub fn bench_day<S: Solution>(c: &mut Criterion, generate: fn(u64, usize) -> String, scales: &[usize]) {
+  let scales = match env::var("AOC_BENCH_SCALES") {
+    Ok(scales) => scales.split(',').map(|scale| scale.trim().parse().expect("AOC_BENCH_SCALES must be a list of numbers")).collect(),
+    Err(_) => scales.to_vec(),
+  };
+
+  let mut inputs = vec![];
+  if let Ok(input) = fs::read_to_string(Day::new::<S>().directory().join("input.txt")) {
+    inputs.push((String::from("input"), input));
+  }
+  inputs.extend(scales.iter().map(|scale| (format!("x{scale}"), generate(u64::from(S::DAY), *scale))));
+
+  for (name, input) in inputs {
+    let started = Instant::now();
+    let parsed = S::parse(input.as_bytes()).unwrap_or_else(|error| panic!("{name}: {error}"));
+    S::part_one(&parsed).unwrap_or_else(|error| panic!("{name}: part one: {error}"));
+    S::part_two(&parsed).unwrap_or_else(|error| panic!("{name}: part two: {error}"));
+    let once = started.elapsed();
+
+    let mut group = c.benchmark_group(format!("day-{:02}", S::DAY));
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    if input.len() > 1_000_000 {
+      group.sample_size(10);
+    }
+    if once > Duration::from_secs(1) {
+      group.sample_size(10).sampling_mode(SamplingMode::Flat).measurement_time(once);
+    }
+
+    group.bench_function(BenchmarkId::new("parse", &name), |b| b.iter(|| S::parse(black_box(input.as_bytes())).unwrap()));
+    group.bench_function(BenchmarkId::new("part one", &name), |b| b.iter(|| S::part_one(black_box(&parsed)).unwrap()));
+    group.bench_function(BenchmarkId::new("part two", &name), |b| b.iter(|| S::part_two(black_box(&parsed)).unwrap()));
+    group.finish();
+  }
+}
@@ -0,0 +1,87 @@
+//! A global allocator that counts live heap bytes, so the runner can report how
+//! much memory each phase needed at its peak. Binaries opt in with
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: aoc_runner::alloc::CountingAllocator = aoc_runner::alloc::CountingAllocator;
+//! ```
+use std::{
+  alloc::{GlobalAlloc, Layout, System},
+  sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+/// [`System`], keeping count of the bytes currently allocated and their peak.
+pub struct CountingAllocator;
+
+fn grow(bytes: usize) {
+  let current = CURRENT.fetch_add(bytes, Ordering::Relaxed) + bytes;
+  PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn shrink(bytes: usize) {
+  CURRENT.fetch_sub(bytes, Ordering::Relaxed);
+}
+
+// SAFETY: every call is forwarded unchanged to `System`, the counters only
+// observe sizes
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    COUNTING.store(true, Ordering::Relaxed);
+    let pointer = System.alloc(layout);
+    if !pointer.is_null() {
+      grow(layout.size());
+    }
+    pointer
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    COUNTING.store(true, Ordering::Relaxed);
+    let pointer = System.alloc_zeroed(layout);
+    if !pointer.is_null() {
+      grow(layout.size());
+    }
+    pointer
+  }
+
+  unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+    System.dealloc(pointer, layout);
+    shrink(layout.size());
+  }
+
+  unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    let new_pointer = System.realloc(pointer, layout, new_size);
+    if !new_pointer.is_null() {
+      if new_size > layout.size() {
+        grow(new_size - layout.size());
+      } else {
+        shrink(layout.size() - new_size);
+      }
+    }
+    new_pointer
+  }
+}
+
+/// Whether [`CountingAllocator`] is the global allocator of this program.
+#[must_use]
+pub fn is_counting() -> bool {
+  COUNTING.load(Ordering::Relaxed)
+}
+
+/// Starts a new measurement: the peak drops to what is allocated right now,
+/// which is returned.
+#[must_use]
+pub fn reset_peak() -> usize {
+  let current = CURRENT.load(Ordering::Relaxed);
+  PEAK.store(current, Ordering::Relaxed);
+  current
+}
+
+/// The most bytes allocated at once since the last [`reset_peak`].
+#[must_use]
+pub fn peak() -> usize {
+  PEAK.load(Ordering::Relaxed)
+}
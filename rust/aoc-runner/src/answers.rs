@@ -0,0 +1,286 @@
+use std::{
+  fmt::{self, Display, Write},
+  str::FromStr,
+  time::Duration,
+};
+
+/// How long each phase of a run took.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+  pub parse: Duration,
+  pub part_one: Duration,
+  pub part_two: Duration,
+}
+
+impl Timings {
+  #[must_use]
+  pub fn total(&self) -> Duration {
+    self.parse + self.part_one + self.part_two
+  }
+
+  /// The minimum, median and maximum of each phase on its own, or `None` if
+  /// there are no runs.
+  #[must_use]
+  pub fn summarize(runs: &[Timings]) -> Option<(Timings, Timings, Timings)> {
+    let phase = |duration: fn(&Timings) -> Duration| {
+      let mut durations = runs.iter().map(duration).collect::<Vec<_>>();
+      durations.sort_unstable();
+      (durations[0], durations[durations.len() / 2], durations[durations.len() - 1])
+    };
+    if runs.is_empty() {
+      return None;
+    }
+
+    let (parse, part_one, part_two) = (phase(|t| t.parse), phase(|t| t.part_one), phase(|t| t.part_two));
+    Some((
+      Timings { parse: parse.0, part_one: part_one.0, part_two: part_two.0 },
+      Timings { parse: parse.1, part_one: part_one.1, part_two: part_two.1 },
+      Timings { parse: parse.2, part_one: part_one.2, part_two: part_two.2 },
+    ))
+  }
+}
+
+/// The fastest and slowest of several runs, see [`Answers::timings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spread {
+  pub runs: usize,
+  pub min: Timings,
+  pub max: Timings,
+}
+
+/// The most heap each phase had allocated on top of what was already there
+/// when it started, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Heap {
+  pub parse: usize,
+  pub part_one: usize,
+  pub part_two: usize,
+}
+
+/// The answers for one day, rendered to strings so days with different answer
+/// types can be reported side by side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+  pub day: u8,
+  pub part_one: String,
+  pub part_two: String,
+  /// The median of each phase when the day was run several times.
+  pub timings: Timings,
+  /// Set when the day was run more than once.
+  pub spread: Option<Spread>,
+  /// Set when the program counts its allocations, see [`crate::alloc`].
+  pub heap: Option<Heap>,
+}
+
+impl Answers {
+  /// One JSON object on a single line:
+  /// `{"day":1,"part1":"…","part2":"…","parse_ns":…,"part1_ns":…,"part2_ns":…}`.
+  /// Answers are always strings; a multi-line answer (day 10's CRT) also gets
+  /// its rows as an array in `part1_rows` or `part2_rows`. Repeated runs add
+  /// `runs` and `…_ns_min`/`…_ns_max`, and counted allocations `…_heap_bytes`.
+  #[must_use]
+  pub fn to_json(&self) -> String {
+    let mut json = format!("{{\"day\":{}", self.day);
+    for (key, answer) in [("part1", &self.part_one), ("part2", &self.part_two)] {
+      write!(json, ",\"{key}\":{}", json_string(answer)).unwrap();
+      if answer.contains('\n') {
+        let rows = answer.lines().map(json_string).collect::<Vec<_>>();
+        write!(json, ",\"{key}_rows\":[{}]", rows.join(",")).unwrap();
+      }
+    }
+    write!(
+      json,
+      ",\"parse_ns\":{},\"part1_ns\":{},\"part2_ns\":{}",
+      self.timings.parse.as_nanos(),
+      self.timings.part_one.as_nanos(),
+      self.timings.part_two.as_nanos()
+    ).unwrap();
+    if let Some(Spread { runs, min, max }) = self.spread {
+      write!(json, ",\"runs\":{runs}").unwrap();
+      for (key, min, max) in [("parse", min.parse, max.parse), ("part1", min.part_one, max.part_one), ("part2", min.part_two, max.part_two)] {
+        write!(json, ",\"{key}_ns_min\":{},\"{key}_ns_max\":{}", min.as_nanos(), max.as_nanos()).unwrap();
+      }
+    }
+    if let Some(heap) = self.heap {
+      write!(json, ",\"parse_heap_bytes\":{},\"part1_heap_bytes\":{},\"part2_heap_bytes\":{}", heap.parse, heap.part_one, heap.part_two).unwrap();
+    }
+    json.push('}');
+
+    json
+  }
+}
+
+impl Display for Answers {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "day {:02}: part one: ", self.day)?;
+    write_answer(f, &self.part_one)?;
+    write!(f, ", part two: ")?;
+    write_answer(f, &self.part_two)?;
+    write!(f, ", time: {:.2?}", self.timings.total())?;
+    if let Some(spread) = self.spread {
+      write!(f, " (median of {} runs)", spread.runs)?;
+    }
+
+    let (min, max) = (self.spread.map(|spread| spread.min), self.spread.map(|spread| spread.max));
+    let phases = [
+      (", parse", self.timings.parse, min.zip(max).map(|(min, max)| (min.parse, max.parse))),
+      (" / part one", self.timings.part_one, min.zip(max).map(|(min, max)| (min.part_one, max.part_one))),
+      (" / part two", self.timings.part_two, min.zip(max).map(|(min, max)| (min.part_two, max.part_two))),
+    ];
+    for (name, median, range) in phases {
+      write!(f, "{name} {median:.2?}")?;
+      if let Some((min, max)) = range {
+        write!(f, " [{min:.2?}..{max:.2?}]")?;
+      }
+    }
+
+    if let Some(heap) = self.heap {
+      write!(f, ", heap: parse {} / part one {} / part two {}", Bytes(heap.parse), Bytes(heap.part_one), Bytes(heap.part_two))?;
+    }
+
+    Ok(())
+  }
+}
+
+// a byte count in the largest binary unit that keeps it above one
+struct Bytes(usize);
+
+impl Display for Bytes {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if self.0 < 1024 {
+      return write!(f, "{} B", self.0);
+    }
+
+    let mut unit = 0;
+    let mut divisor = 1024;
+    while self.0 / divisor >= 1024 && unit < UNITS.len() - 1 {
+      divisor *= 1024;
+      unit += 1;
+    }
+    let tenths = self.0 / (divisor / 1024) * 10 / 1024;
+    write!(f, "{}.{} {}", tenths / 10, tenths % 10, UNITS[unit])
+  }
+}
+
+// multi-line answers (day 10's CRT) read better starting on their own line
+fn write_answer(f: &mut fmt::Formatter<'_>, answer: &str) -> fmt::Result {
+  if answer.contains('\n') {
+    write!(f, "\n{answer}\n")
+  } else {
+    write!(f, "{answer}")
+  }
+}
+
+pub(crate) fn json_string(text: &str) -> String {
+  let mut json = String::from("\"");
+  for c in text.chars() {
+    match c {
+      '"' => json.push_str("\\\""),
+      '\\' => json.push_str("\\\\"),
+      '\n' => json.push_str("\\n"),
+      '\r' => json.push_str("\\r"),
+      '\t' => json.push_str("\\t"),
+      c if c.is_control() => write!(json, "\\u{:04x}", u32::from(c)).unwrap(),
+      c => json.push(c),
+    }
+  }
+  json.push('"');
+
+  json
+}
+
+/// How answers are printed: the human readable line, or one JSON object per day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+  #[default]
+  Text,
+  Json,
+}
+
+impl Format {
+  #[must_use]
+  pub fn render(self, answers: &Answers) -> String {
+    match self {
+      Format::Text => answers.to_string(),
+      Format::Json => answers.to_json(),
+    }
+  }
+}
+
+impl FromStr for Format {
+  type Err = String;
+
+  fn from_str(format: &str) -> Result<Self, Self::Err> {
+    match format {
+      "text" => Ok(Format::Text),
+      "json" => Ok(Format::Json),
+      _ => Err(format!("unknown format `{format}`, expected `text` or `json`")),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn answers(part_two: &str) -> Answers {
+    Answers {
+      day: 10,
+      part_one: String::from("13140"),
+      part_two: part_two.to_string(),
+      timings: timings(5, 7, 11),
+      spread: None,
+      heap: None,
+    }
+  }
+
+  fn timings(parse: u64, part_one: u64, part_two: u64) -> Timings {
+    Timings { parse: Duration::from_nanos(parse), part_one: Duration::from_nanos(part_one), part_two: Duration::from_nanos(part_two) }
+  }
+
+  #[test]
+  fn json_has_answers_and_timings() {
+    assert_eq!(
+      answers("MCD").to_json(),
+      r#"{"day":10,"part1":"13140","part2":"MCD","parse_ns":5,"part1_ns":7,"part2_ns":11}"#
+    );
+  }
+
+  #[test]
+  fn json_splits_multi_line_answers_into_rows() {
+    assert_eq!(
+      answers("█ \"\n █").to_json(),
+      r#"{"day":10,"part1":"13140","part2":"█ \"\n █","part2_rows":["█ \""," █"],"parse_ns":5,"part1_ns":7,"part2_ns":11}"#
+    );
+  }
+
+  #[test]
+  fn json_has_spread_and_heap_when_measured() {
+    let mut answers = answers("MCD");
+    answers.spread = Some(Spread { runs: 3, min: timings(4, 6, 10), max: timings(9, 8, 12) });
+    answers.heap = Some(Heap { parse: 1024, part_one: 0, part_two: 16 });
+    assert_eq!(
+      answers.to_json(),
+      concat!(
+        r#"{"day":10,"part1":"13140","part2":"MCD","parse_ns":5,"part1_ns":7,"part2_ns":11,"runs":3,"#,
+        r#""parse_ns_min":4,"parse_ns_max":9,"part1_ns_min":6,"part1_ns_max":8,"part2_ns_min":10,"part2_ns_max":12,"#,
+        r#""parse_heap_bytes":1024,"part1_heap_bytes":0,"part2_heap_bytes":16}"#
+      )
+    );
+  }
+
+  #[test]
+  fn summarize_each_phase_on_its_own() {
+    let runs = [timings(3, 10, 1), timings(1, 30, 2), timings(2, 20, 3)];
+    assert_eq!(Timings::summarize(&runs), Some((timings(1, 10, 1), timings(2, 20, 2), timings(3, 30, 3))));
+    assert_eq!(Timings::summarize(&[]), None);
+  }
+
+  #[test]
+  fn bytes_in_binary_units() {
+    assert_eq!(Bytes(512).to_string(), "512 B");
+    assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+    assert_eq!(Bytes(3 * 1024 * 1024).to_string(), "3.0 MiB");
+  }
+}
@@ -0,0 +1,282 @@
+//! Solving one day for many inputs at once, e.g. every team member's, with one
+//! row per input in the report and a failing input never stopping the others.
+use std::{
+  fmt::{self, Display, Write},
+  fs,
+  panic::{self, AssertUnwindSafe},
+  path::{Path, PathBuf},
+  sync::atomic::{AtomicUsize, Ordering},
+  thread,
+  time::Duration,
+};
+
+use crate::{answers::json_string, Answers, DayError, Format};
+
+/// Why one input of a batch has no answers.
+#[derive(Debug)]
+pub enum Failure {
+  Error(DayError),
+  /// The solver panicked, with the panic's message.
+  Panic(String),
+}
+
+impl Display for Failure {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Failure::Error(error) => write!(f, "{error}"),
+      Failure::Panic(message) => write!(f, "panicked: {message}"),
+    }
+  }
+}
+
+impl std::error::Error for Failure {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Failure::Error(error) => Some(error),
+      Failure::Panic(_) => None,
+    }
+  }
+}
+
+/// One input of a batch and how solving it went.
+#[derive(Debug)]
+pub struct Row {
+  pub input: PathBuf,
+  pub result: Result<Answers, Failure>,
+}
+
+impl Row {
+  /// [`Answers::to_json`] with the input first, `{"input":"…","day":1,…}`, or
+  /// `{"input":"…","error":"…"}` if it failed.
+  #[must_use]
+  pub fn to_json(&self) -> String {
+    let input = json_string(&self.input.display().to_string());
+    match &self.result {
+      Ok(answers) => format!("{{\"input\":{input},{}", &answers.to_json()[1..]),
+      Err(failure) => format!("{{\"input\":{input},\"error\":{}}}", json_string(&failure.to_string())),
+    }
+  }
+}
+
+/// Whether `inputs` name more than one input: several of them, a directory or
+/// a glob pattern.
+#[must_use]
+pub fn is_batch(inputs: &[String]) -> bool {
+  match inputs {
+    [input] => is_pattern(input) || Path::new(input).is_dir(),
+    _ => true,
+  }
+}
+
+fn is_pattern(input: &str) -> bool {
+  input.contains(['*', '?', '['])
+}
+
+/// The input files named by `inputs`, in order: every file directly inside a
+/// directory, every file matching a glob pattern like `inputs/*.txt`, and any
+/// other path as it is, so a missing file fails on its own row.
+///
+/// # Errors
+///
+/// Returns an error if a directory cannot be read, or a pattern is invalid or
+/// matches nothing.
+pub fn inputs(inputs: &[String]) -> Result<Vec<PathBuf>, String> {
+  let mut files = vec![];
+  for input in inputs {
+    let path = Path::new(input);
+    let mut matched = if path.is_dir() {
+      let entries = fs::read_dir(path).map_err(|error| format!("cannot read `{input}`: {error}"))?;
+      entries.filter_map(Result::ok).map(|entry| entry.path()).filter(|path| path.is_file()).collect::<Vec<_>>()
+    } else if is_pattern(input) {
+      let paths = glob::glob(input).map_err(|error| format!("invalid pattern `{input}`: {error}"))?;
+      let matched = paths.filter_map(Result::ok).filter(|path| path.is_file()).collect::<Vec<_>>();
+      if matched.is_empty() {
+        return Err(format!("no inputs match `{input}`"));
+      }
+      matched
+    } else {
+      vec![path.to_path_buf()]
+    };
+    matched.sort();
+    files.append(&mut matched);
+  }
+
+  Ok(files)
+}
+
+/// The number of inputs to solve at once when not told otherwise.
+#[must_use]
+pub fn default_jobs() -> usize {
+  thread::available_parallelism().map_or(1, usize::from)
+}
+
+/// Solves every input with `solve`, up to `jobs` at a time, returning a row per
+/// input in the same order. An error or a panic only fails that input's row.
+///
+/// Inputs solved side by side slow each other down, so use one job for timings
+/// that compare with a single run. The heap is counted for the whole process,
+/// so with more than one job it is left out of the answers.
+pub fn run<F>(inputs: &[PathBuf], jobs: usize, solve: F) -> Vec<Row>
+where
+  F: Fn(&Path) -> Result<Answers, DayError> + Sync,
+{
+  let jobs = jobs.clamp(1, inputs.len().max(1));
+  let next = AtomicUsize::new(0);
+  let mut rows = thread::scope(|scope| {
+    let workers = (0..jobs).map(|_| scope.spawn(|| {
+      let mut rows = vec![];
+      loop {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        let Some(input) = inputs.get(index) else { break };
+        let result = match panic::catch_unwind(AssertUnwindSafe(|| solve(input))) {
+          Ok(Ok(answers)) if jobs > 1 => Ok(Answers { heap: None, ..answers }),
+          Ok(Ok(answers)) => Ok(answers),
+          Ok(Err(error)) => Err(Failure::Error(error)),
+          Err(panic) => Err(Failure::Panic(panic_message(panic.as_ref()))),
+        };
+        rows.push((index, Row { input: input.clone(), result }));
+      }
+      rows
+    })).collect::<Vec<_>>();
+
+    workers.into_iter().flat_map(|worker| worker.join().unwrap_or_default()).collect::<Vec<_>>()
+  });
+  rows.sort_by_key(|(index, _)| *index);
+
+  rows.into_iter().map(|(_, row)| row).collect()
+}
+
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+  match (panic.downcast_ref::<&str>(), panic.downcast_ref::<String>()) {
+    (Some(message), _) => (*message).to_string(),
+    (_, Some(message)) => message.clone(),
+    _ => String::from("unknown panic"),
+  }
+}
+
+/// The rows as a table with a line of totals, or as one JSON object per line.
+#[must_use]
+pub fn report(rows: &[Row], format: Format) -> String {
+  match format {
+    Format::Text => table(rows),
+    Format::Json => rows.iter().map(Row::to_json).collect::<Vec<_>>().join("\n"),
+  }
+}
+
+// a multi-line answer, i.e. day 10's CRT, does not fit in a cell
+fn cell(answer: &str) -> &str {
+  if answer.contains('\n') { "(drawing)" } else { answer }
+}
+
+fn table(rows: &[Row]) -> String {
+  let answers = rows.iter().filter_map(|row| row.result.as_ref().ok());
+  let width = |column: &dyn Fn(&Answers) -> &str, header: &str| answers.clone().map(|answers| cell(column(answers)).len()).chain([header.len()]).max().unwrap_or_default();
+  let input_width = rows.iter().map(|row| row.input.display().to_string().len()).chain(["input".len()]).max().unwrap_or_default();
+  let part_one_width = width(&|answers| &answers.part_one, "part one");
+  let part_two_width = width(&|answers| &answers.part_two, "part two");
+
+  let mut table = format!("{:input_width$}  {:part_one_width$}  {:part_two_width$}  time\n", "input", "part one", "part two");
+  for row in rows {
+    let line = match &row.result {
+      Ok(answers) => format!(
+        "{:input_width$}  {:part_one_width$}  {:part_two_width$}  {:.2?}",
+        row.input.display(),
+        cell(&answers.part_one),
+        cell(&answers.part_two),
+        answers.timings.total()
+      ),
+      Err(failure) => format!("{:input_width$}  error: {failure}", row.input.display()),
+    };
+    writeln!(table, "{}", line.trim_end()).unwrap();
+  }
+
+  let failures = rows.iter().filter(|row| row.result.is_err()).count();
+  let total = answers.clone().map(|answers| answers.timings.total()).sum::<Duration>();
+  write!(table, "\n{} inputs, {failures} failed, {total:.2?} solving", rows.len()).unwrap();
+  let slowest = rows.iter().filter_map(|row| Some((&row.input, row.result.as_ref().ok()?.timings.total()))).max_by_key(|(_, time)| *time);
+  if let Some((input, time)) = slowest {
+    write!(table, ", slowest {} in {time:.2?}", input.display()).unwrap();
+  }
+
+  table
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::Timings;
+
+  fn answers(part_one: &str) -> Answers {
+    Answers {
+      day: 1,
+      part_one: part_one.to_string(),
+      part_two: String::from("2"),
+      timings: Timings { parse: Duration::from_millis(part_one.len() as u64), ..Timings::default() },
+      spread: None,
+      heap: None,
+    }
+  }
+
+  fn solve(input: &Path) -> Result<Answers, DayError> {
+    match input.to_str() {
+      Some("panics") => panic!("out of snacks"),
+      Some("malformed") => Err(DayError::parse("4OOO", "4OOO", "invalid calorie count").on_line(6).in_file(input)),
+      Some(name) => Ok(answers(name)),
+      None => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn failures_stay_on_their_row() {
+    let inputs = ["a", "panics", "malformed", "b"].map(PathBuf::from);
+    let rows = run(&inputs, 3, solve);
+    assert_eq!(rows.iter().map(|row| row.input.clone()).collect::<Vec<_>>(), inputs);
+    assert_eq!(rows[0].result.as_ref().unwrap().part_one, "a");
+    assert_eq!(rows[1].result.as_ref().unwrap_err().to_string(), "panicked: out of snacks");
+    assert_eq!(rows[2].result.as_ref().unwrap_err().to_string(), "malformed:6:1: invalid calorie count: `4OOO`");
+    assert_eq!(rows[3].result.as_ref().unwrap().part_one, "b");
+  }
+
+  #[test]
+  fn table_of_answers() {
+    let rows = run(&["first", "malformed", "second.txt"].map(PathBuf::from), 1, solve);
+    assert_eq!(
+      report(&rows, Format::Text),
+      "\
+input       part one    part two  time
+first       first       2         5.00ms
+malformed   error: malformed:6:1: invalid calorie count: `4OOO`
+second.txt  second.txt  2         10.00ms
+
+3 inputs, 1 failed, 15.00ms solving, slowest second.txt in 10.00ms"
+    );
+  }
+
+  #[test]
+  fn json_lines() {
+    let rows = run(&["a", "malformed"].map(PathBuf::from), 2, solve);
+    let json = report(&rows, Format::Json);
+    let lines = json.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with(r#"{"input":"a","day":1,"part1":"a","part2":"2","parse_ns":1000000"#), "{json}");
+    assert_eq!(lines[1], r#"{"input":"malformed","error":"malformed:6:1: invalid calorie count: `4OOO`"}"#);
+  }
+
+  #[test]
+  fn directories_and_patterns() {
+    let root = std::env::temp_dir().join(format!("aoc-test-batch-{}", std::process::id()));
+    fs::create_dir_all(root.join("inputs/nested")).unwrap();
+    for name in ["inputs/b.txt", "inputs/a.txt", "inputs/notes.md", "c.txt"] {
+      fs::write(root.join(name), "").unwrap();
+    }
+    let name = |path: &str| root.join(path).display().to_string();
+
+    let files = inputs(&[name("inputs"), name("*.txt"), name("missing.txt")]).unwrap();
+    assert_eq!(files, ["inputs/a.txt", "inputs/b.txt", "inputs/notes.md", "c.txt", "missing.txt"].map(|path| root.join(path)));
+    assert_eq!(inputs(&[name("inputs/*.txt")]).unwrap(), ["inputs/a.txt", "inputs/b.txt"].map(|path| root.join(path)));
+    assert_eq!(inputs(&[name("*.csv")]).unwrap_err(), format!("no inputs match `{}`", name("*.csv")));
+
+    assert!(is_batch(&[name("inputs")]) && is_batch(&[name("*.txt")]) && is_batch(&[name("c.txt"), name("c.txt")]));
+    assert!(!is_batch(&[name("c.txt")]));
+    fs::remove_dir_all(root).unwrap();
+  }
+}
@@ -0,0 +1,136 @@
+use std::{
+  error::Error,
+  fmt::{self, Display},
+  io,
+  path::{Path, PathBuf},
+};
+
+/// Everything that can go wrong while solving a day: the input could not be
+/// read, it could not be parsed, or it parsed fine but has no answer.
+#[derive(Debug)]
+pub enum DayError {
+  Io {
+    file: Option<PathBuf>,
+    source: io::Error,
+  },
+  Parse {
+    file: Option<PathBuf>,
+    line: usize,
+    column: usize,
+    text: String,
+    message: String,
+  },
+  Unsolvable(String),
+}
+
+impl DayError {
+  /// A parse error pointing at `token`, which should be a slice of `source_line`
+  /// so its column can be worked out. The line number is filled in later with
+  /// [`DayError::on_line`] by whoever is walking the input.
+  #[must_use]
+  pub fn parse(source_line: &str, token: &str, message: impl Into<String>) -> Self {
+    DayError::Parse {
+      file: None,
+      line: 0,
+      column: column_of(source_line, token),
+      text: token.to_string(),
+      message: message.into(),
+    }
+  }
+
+  #[must_use]
+  pub fn unsolvable(message: impl Into<String>) -> Self {
+    DayError::Unsolvable(message.into())
+  }
+
+  /// Sets the (1-based) line number of a parse error.
+  #[must_use]
+  pub fn on_line(mut self, number: usize) -> Self {
+    if let DayError::Parse { line, .. } = &mut self {
+      *line = number;
+    }
+
+    self
+  }
+
+  /// Records which file the input came from, `-` being stdin.
+  #[must_use]
+  pub fn in_file(mut self, path: impl AsRef<Path>) -> Self {
+    let path = path.as_ref();
+    let path = if path == Path::new("-") { Path::new("<stdin>") } else { path };
+    if let DayError::Io { file, .. } | DayError::Parse { file, .. } = &mut self {
+      *file = Some(path.to_path_buf());
+    }
+
+    self
+  }
+}
+
+// 1-based column of `token` inside `line`, falling back to the start of the line
+// when `token` was not sliced out of it
+fn column_of(line: &str, token: &str) -> usize {
+  let start = line.as_ptr() as usize;
+  let position = token.as_ptr() as usize;
+  if position >= start && position + token.len() <= start + line.len() {
+    line[..position - start].chars().count() + 1
+  } else {
+    1
+  }
+}
+
+impl From<io::Error> for DayError {
+  fn from(source: io::Error) -> Self {
+    DayError::Io { file: None, source }
+  }
+}
+
+impl Display for DayError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DayError::Io { file: Some(file), source } => write!(f, "{}: {source}", file.display()),
+      DayError::Io { file: None, source } => write!(f, "{source}"),
+      DayError::Parse { file, line, column, text, message } => {
+        match file {
+          Some(file) => write!(f, "{}:{line}:{column}: ", file.display())?,
+          None => write!(f, "line {line}, column {column}: ")?,
+        }
+
+        write!(f, "{message}: `{text}`")
+      }
+      DayError::Unsolvable(message) => write!(f, "{message}"),
+    }
+  }
+}
+
+impl Error for DayError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      DayError::Io { source, .. } => Some(source),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_error_points_at_token() {
+    let line = "move 1 from x to 3";
+    let error = DayError::parse(line, &line[12..13], "not a stack").on_line(4).in_file("input.txt");
+    assert_eq!(error.to_string(), "input.txt:4:13: not a stack: `x`");
+  }
+
+  #[test]
+  fn parse_error_without_file() {
+    let error = DayError::parse("Q X", "Q", "invalid choice").on_line(2);
+    assert_eq!(error.to_string(), "line 2, column 1: invalid choice: `Q`");
+  }
+
+  #[test]
+  fn token_outside_line_falls_back_to_first_column() {
+    let error = DayError::parse("abc", "zzz", "oops").on_line(1);
+    assert!(matches!(error, DayError::Parse { column: 1, .. }));
+  }
+}
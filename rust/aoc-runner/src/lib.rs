@@ -0,0 +1,363 @@
+#![warn(clippy::all, clippy::pedantic)]
+pub mod alloc;
+mod answers;
+pub mod batch;
+mod error;
+pub mod parsing;
+pub mod registry;
+mod rng;
+pub mod trace;
+
+use std::{
+  env,
+  fmt::Display,
+  fs::File,
+  io::{self, BufRead, BufReader, Read},
+  path::{Path, PathBuf},
+  process,
+  time::Instant,
+};
+
+pub use answers::{Answers, Format, Heap, Spread, Timings};
+pub use error::DayError;
+pub use rng::Rng;
+
+/// A single day of the calendar, split into parsing and the two puzzle parts so
+/// the runner can drive every day the same way.
+pub trait Solution {
+  const DAY: u8;
+
+  type Input;
+  type PartOne: Display;
+  type PartTwo: Display;
+
+  /// # Errors
+  ///
+  /// Returns an error if the input cannot be read or is malformed.
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError>;
+
+  /// # Errors
+  ///
+  /// Returns an error if the input has no answer.
+  fn part_one(input: &Self::Input) -> Result<Self::PartOne, DayError>;
+
+  /// # Errors
+  ///
+  /// Returns an error if the input has no answer.
+  fn part_two(input: &Self::Input) -> Result<Self::PartTwo, DayError>;
+}
+
+/// Opens a puzzle input for reading, where `-` means stdin.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened.
+pub fn open(filename: &Path) -> Result<Box<dyn BufRead>, DayError> {
+  if filename == Path::new("-") {
+    return Ok(Box::new(io::stdin().lock()));
+  }
+
+  let file = File::open(filename).map_err(|e| DayError::from(e).in_file(filename))?;
+  Ok(Box::new(BufReader::new(file)))
+}
+
+/// Parses everything `reader` yields and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader<S: Solution>(reader: impl BufRead) -> Result<(S::PartOne, S::PartTwo), DayError> {
+  let input = S::parse(reader)?;
+
+  Ok((S::part_one(&input)?, S::part_two(&input)?))
+}
+
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str<S: Solution>(input: &str) -> Result<(S::PartOne, S::PartTwo), DayError> {
+  solve_reader::<S>(input.as_bytes())
+}
+
+/// Like [`solve_reader`], reading from `filename` (or stdin for `-`) and tagging
+/// any error with where the input came from.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is malformed, or has no answer.
+pub fn solve<S: Solution>(filename: &Path) -> Result<(S::PartOne, S::PartTwo), DayError> {
+  solve_reader::<S>(open(filename)?).map_err(|e| e.in_file(filename))
+}
+
+/// Like [`solve`], but times parsing and each part separately and renders the
+/// answers.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is malformed, or has no answer.
+pub fn run<S: Solution>(filename: &Path) -> Result<Answers, DayError> {
+  run_repeated::<S>(filename, 1)
+}
+
+/// Like [`run`], but solves the input `repeat` times, reporting the median time
+/// of each phase along with the fastest and slowest. The input is read into
+/// memory first, so reading it is never part of the timings.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is malformed, or has no answer.
+pub fn run_repeated<S: Solution>(filename: &Path, repeat: usize) -> Result<Answers, DayError> {
+  let mut bytes = vec![];
+  open(filename)?.read_to_end(&mut bytes).map_err(|e| DayError::from(e).in_file(filename))?;
+
+  let mut runs = vec![];
+  let mut heap = Heap::default();
+  let mut answers = (String::new(), String::new());
+  for _ in 0..repeat.max(1) {
+    let (timings, run_heap, part_one, part_two) = measure::<S>(&bytes).map_err(|e| e.in_file(filename))?;
+    runs.push(timings);
+    heap = Heap { parse: heap.parse.max(run_heap.parse), part_one: heap.part_one.max(run_heap.part_one), part_two: heap.part_two.max(run_heap.part_two) };
+    answers = (part_one, part_two);
+  }
+
+  let Some((min, median, max)) = Timings::summarize(&runs) else { unreachable!("there is always at least one run") };
+  Ok(Answers {
+    day: S::DAY,
+    part_one: answers.0,
+    part_two: answers.1,
+    timings: median,
+    spread: (runs.len() > 1).then_some(Spread { runs: runs.len(), min, max }),
+    heap: alloc::is_counting().then_some(heap),
+  })
+}
+
+// one timed run, with the heap each phase needed on top of what it started with
+fn measure<S: Solution>(bytes: &[u8]) -> Result<(Timings, Heap, String, String), DayError> {
+  let _day = tracing::info_span!("day", day = S::DAY).entered();
+
+  let span = tracing::info_span!("parse").entered();
+  let base = alloc::reset_peak();
+  let time = Instant::now();
+  let input = S::parse(bytes)?;
+  let parse = time.elapsed();
+  let parse_heap = alloc::peak() - base;
+  drop(span);
+
+  let span = tracing::info_span!("part_one").entered();
+  let base = alloc::reset_peak();
+  let time = Instant::now();
+  let part_one = S::part_one(&input)?;
+  let part_one_time = time.elapsed();
+  let part_one_heap = alloc::peak() - base;
+  drop(span);
+
+  let span = tracing::info_span!("part_two").entered();
+  let base = alloc::reset_peak();
+  let time = Instant::now();
+  let part_two = S::part_two(&input)?;
+  let part_two_time = time.elapsed();
+  let part_two_heap = alloc::peak() - base;
+  drop(span);
+
+  Ok((
+    Timings { parse, part_one: part_one_time, part_two: part_two_time },
+    Heap { parse: parse_heap, part_one: part_one_heap, part_two: part_two_heap },
+    part_one.to_string(),
+    part_two.to_string(),
+  ))
+}
+
+fn solve_to_strings<S: Solution>(input: &str) -> Result<(String, String), DayError> {
+  let (part_one, part_two) = solve_str::<S>(input)?;
+  Ok((part_one.to_string(), part_two.to_string()))
+}
+
+/// A type-erased handle on a [`Solution`], so the `aoc` binary can keep every
+/// day in one table.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+  pub number: u8,
+  run: fn(&Path, usize) -> Result<Answers, DayError>,
+  solve: fn(&str) -> Result<(String, String), DayError>,
+  generate: Option<fn(u64, usize) -> String>,
+}
+
+impl Day {
+  #[must_use]
+  pub const fn new<S: Solution>() -> Self {
+    Day { number: S::DAY, run: run_repeated::<S>, solve: solve_to_strings::<S>, generate: None }
+  }
+
+  /// Attaches the day's input generator, taking a seed and a scale.
+  #[must_use]
+  pub const fn with_generator(mut self, generate: fn(u64, usize) -> String) -> Self {
+    self.generate = Some(generate);
+    self
+  }
+
+  /// # Errors
+  ///
+  /// Returns an error if the file cannot be read, is malformed, or has no answer.
+  pub fn run(&self, filename: &Path) -> Result<Answers, DayError> {
+    (self.run)(filename, 1)
+  }
+
+  /// See [`run_repeated`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the file cannot be read, is malformed, or has no answer.
+  pub fn run_repeated(&self, filename: &Path, repeat: usize) -> Result<Answers, DayError> {
+    (self.run)(filename, repeat)
+  }
+
+  /// The answers for `input`, without timing anything, so it also works where
+  /// there is no clock, e.g. in WebAssembly.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input is malformed or has no answer.
+  pub fn solve_str(&self, input: &str) -> Result<(String, String), DayError> {
+    (self.solve)(input)
+  }
+
+  /// A synthetic input roughly `scale` times the size of a real one, the same
+  /// for the same `seed`, or `None` if the day has no generator.
+  #[must_use]
+  pub fn generate(&self, seed: u64, scale: usize) -> Option<String> {
+    self.generate.map(|generate| generate(seed, scale))
+  }
+
+  /// The crate directory of this day, e.g. `rust/day-07`.
+  #[must_use]
+  pub fn directory(&self) -> PathBuf {
+    workspace_root().join(format!("day-{:02}", self.number))
+  }
+}
+
+#[must_use]
+pub fn workspace_root() -> PathBuf {
+  let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+  manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf()
+}
+
+/// Entry point shared by every `day-NN` binary:
+/// `day-NN [--format text|json] [--repeat N] [--jobs N] [--trace FILTER [--trace-format text|json]] <input file | ->`.
+/// Several inputs, a directory or a glob pattern solve them all as a batch, see [`batch`].
+/// A flag's value can also follow an `=`, e.g. `--trace=day11=debug`.
+pub fn main<S: Solution>() {
+  let Arguments { format, repeat, jobs, trace, trace_format, inputs } = arguments(env::args().skip(1)).unwrap_or_else(|error| {
+    if let Some(error) = error {
+      eprintln!("error: {error}");
+    }
+    usage(S::DAY);
+  });
+  if let Some(filter) = trace {
+    if let Err(error) = trace::init(&filter, trace_format) {
+      eprintln!("error: {error}");
+      usage(S::DAY);
+    }
+  }
+
+  if batch::is_batch(&inputs) {
+    let files = batch::inputs(&inputs).unwrap_or_else(|error| {
+      eprintln!("error: {error}");
+      process::exit(2);
+    });
+    let rows = batch::run(&files, jobs.unwrap_or_else(batch::default_jobs), |input| run_repeated::<S>(input, repeat));
+    println!("{}", batch::report(&rows, format));
+    if rows.iter().any(|row| row.result.is_err()) {
+      process::exit(1);
+    }
+    return;
+  }
+
+  match run_repeated::<S>(Path::new(&inputs[0]), repeat) {
+    Ok(answers) => println!("{}", format.render(&answers)),
+    Err(error) => {
+      eprintln!("error: {error}");
+      process::exit(1);
+    }
+  }
+}
+
+// the command line of a day's binary
+#[derive(Debug, PartialEq, Eq)]
+struct Arguments {
+  format: Format,
+  repeat: usize,
+  jobs: Option<usize>,
+  trace: Option<String>,
+  trace_format: Format,
+  inputs: Vec<String>,
+}
+
+// `Err` for a command line `main` cannot run, with the reason if there is more
+// to it than the usage; every flag taking a value takes it as the next argument
+// or after `=`, e.g. `--trace=day11=debug`
+fn arguments(args: impl IntoIterator<Item = String>) -> Result<Arguments, Option<String>> {
+  let mut arguments = Arguments { format: Format::Text, repeat: 1, jobs: None, trace: None, trace_format: Format::Text, inputs: vec![] };
+  let mut args = args.into_iter();
+  while let Some(arg) = args.next() {
+    let (flag, inline) = match arg.split_once('=') {
+      Some((flag, inline)) if flag.starts_with("--") => (flag, Some(inline.to_string())),
+      _ => (arg.as_str(), None),
+    };
+    let mut value = || inline.clone().or_else(|| args.next()).ok_or(None);
+    match flag {
+      "--format" => arguments.format = value()?.parse().map_err(Some)?,
+      "--repeat" => arguments.repeat = value()?.parse().ok().filter(|&repeat| repeat > 0).ok_or(None)?,
+      "--jobs" => arguments.jobs = Some(value()?.parse().ok().filter(|&jobs| jobs > 0).ok_or(None)?),
+      "--trace" => arguments.trace = Some(value()?),
+      "--trace-format" => arguments.trace_format = value()?.parse().map_err(Some)?,
+      _ => arguments.inputs.push(arg),
+    }
+  }
+  if arguments.inputs.is_empty() {
+    return Err(None);
+  }
+
+  Ok(arguments)
+}
+
+fn usage(day: u8) -> ! {
+  eprintln!("usage: day-{day:02} [--format text|json] [--repeat N] [--jobs N] [--trace FILTER [--trace-format text|json]] <input file | -> | <input files, directories or glob patterns>...");
+  process::exit(2);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(args: &str) -> Result<Arguments, Option<String>> {
+    arguments(args.split(' ').map(String::from))
+  }
+
+  #[test]
+  fn flags_with_values() {
+    let expected = Arguments {
+      format: Format::Json,
+      repeat: 3,
+      jobs: Some(2),
+      trace: Some(String::from("day11=debug")),
+      trace_format: Format::Json,
+      inputs: vec![String::from("input.txt")],
+    };
+    assert_eq!(parse("--format json --repeat 3 --jobs 2 --trace day11=debug --trace-format json input.txt"), Ok(expected));
+  }
+
+  #[test]
+  fn flags_with_equals() {
+    let arguments = parse("--trace=day11=debug --trace-format=json --format=json --repeat=3 --jobs=2 input.txt").unwrap();
+    assert_eq!((arguments.trace.as_deref(), arguments.trace_format, arguments.format), (Some("day11=debug"), Format::Json, Format::Json));
+    assert_eq!((arguments.repeat, arguments.jobs, arguments.inputs), (3, Some(2), vec![String::from("input.txt")]));
+  }
+
+  #[test]
+  fn malformed_flags() {
+    assert_eq!(parse("--repeat=0 input.txt"), Err(None));
+    assert_eq!(parse("input.txt --trace"), Err(None));
+    assert_eq!(parse("--format=yaml input.txt").unwrap_err().map(|error| error.contains("yaml")), Some(true));
+    assert_eq!(parse("--jobs 2"), Err(None));
+  }
+}
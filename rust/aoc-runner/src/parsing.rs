@@ -0,0 +1,270 @@
+//! The pieces the days' parsers are built from: numbered lines, groups of them
+//! separated by blank lines, and [`Span`]s to take a line apart with, so every
+//! error points at the text it is about.
+use std::{io::BufRead, str::FromStr};
+
+use crate::DayError;
+
+/// One line of the input with its 1-based number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+  number: usize,
+  text: String,
+}
+
+impl Line {
+  #[must_use]
+  pub fn new(number: usize, text: impl Into<String>) -> Self {
+    Line { number, text: text.into() }
+  }
+
+  #[must_use]
+  pub fn number(&self) -> usize {
+    self.number
+  }
+
+  #[must_use]
+  pub fn text(&self) -> &str {
+    &self.text
+  }
+
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.text.is_empty()
+  }
+
+  /// The whole line, to take apart.
+  #[must_use]
+  pub fn span(&self) -> Span<'_> {
+    Span::new(&self.text, self.number)
+  }
+}
+
+/// Every line of `reader`, numbered from 1.
+pub fn lines(reader: impl BufRead) -> impl Iterator<Item = Result<Line, DayError>> {
+  (1..).zip(reader.lines()).map(|(number, text)| Ok(Line { number, text: text? }))
+}
+
+/// The lines of `reader` split at every blank line, which are left out. Like
+/// `str::split`, `n` blank lines make `n + 1` groups, so two blank lines in a
+/// row or one at the very end make an empty group.
+pub fn groups(reader: impl BufRead) -> impl Iterator<Item = Result<Vec<Line>, DayError>> {
+  let mut lines = lines(reader);
+  let mut done = false;
+  std::iter::from_fn(move || {
+    if done {
+      return None;
+    }
+
+    let mut group = vec![];
+    loop {
+      match lines.next() {
+        Some(Ok(line)) if line.is_empty() => return Some(Ok(group)),
+        Some(Ok(line)) => group.push(line),
+        Some(Err(error)) => {
+          done = true;
+          return Some(Err(error));
+        }
+        None => {
+          done = true;
+          return Some(Ok(group));
+        }
+      }
+    }
+  })
+}
+
+/// A piece of one line of the input. Splitting it gives smaller spans of the
+/// same line, and an error about any of them points at its column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+  line: &'a str,
+  text: &'a str,
+  number: usize,
+}
+
+impl<'a> Span<'a> {
+  /// All of `line`, line `number` of the input.
+  #[must_use]
+  pub fn new(line: &'a str, number: usize) -> Self {
+    Span { line, text: line, number }
+  }
+
+  // `text` has to be a slice of `self.text`
+  fn to(self, text: &'a str) -> Self {
+    Span { text, ..self }
+  }
+
+  #[must_use]
+  pub fn as_str(self) -> &'a str {
+    self.text
+  }
+
+  #[must_use]
+  pub fn is_empty(self) -> bool {
+    self.text.is_empty()
+  }
+
+  /// A parse error pointing at this span.
+  #[must_use]
+  pub fn error(self, message: impl Into<String>) -> DayError {
+    DayError::parse(self.line, self.text, message).on_line(self.number)
+  }
+
+  #[must_use]
+  pub fn trim(self) -> Self {
+    self.to(self.text.trim())
+  }
+
+  /// The span as a number, or anything else `FromStr`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `message` pointing at the span if it does not parse.
+  pub fn parse<T: FromStr>(self, message: &str) -> Result<T, DayError> {
+    self.text.parse().map_err(|_| self.error(message))
+  }
+
+  /// The value paired with the span's text in `choices`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `message` pointing at the span if it is none of them.
+  pub fn one_of<T: Clone>(self, choices: &[(&str, T)], message: &str) -> Result<T, DayError> {
+    choices.iter().find(|(text, _)| *text == self.text).map(|(_, value)| value.clone()).ok_or_else(|| self.error(message))
+  }
+
+  /// What follows `keyword` and the whitespace after it, e.g. `Test: divisible by`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error pointing at the first word if the span does not start
+  /// with `keyword` as a whole word.
+  pub fn keyword(self, keyword: &str) -> Result<Self, DayError> {
+    match self.text.strip_prefix(keyword) {
+      Some(rest) if rest.is_empty() || rest.starts_with(char::is_whitespace) => Ok(self.to(rest.trim_start())),
+      _ => Err(self.words().next().unwrap_or(self).error(format!("expected `{keyword}`"))),
+    }
+  }
+
+  /// The parts before and after the first `separator`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `message` pointing at the span if there is no `separator`.
+  pub fn split_once(self, separator: &str, message: &str) -> Result<(Self, Self), DayError> {
+    let (before, after) = self.text.split_once(separator).ok_or_else(|| self.error(message))?;
+    Ok((self.to(before), self.to(after)))
+  }
+
+  /// The parts between each `separator`.
+  pub fn split(self, separator: &'a str) -> impl Iterator<Item = Self> + 'a {
+    self.text.split(separator).map(move |part| self.to(part))
+  }
+
+  /// The whitespace separated words.
+  pub fn words(self) -> impl Iterator<Item = Self> + 'a {
+    self.text.split_whitespace().map(move |word| self.to(word))
+  }
+
+  /// The `index`th word, counting from 0.
+  ///
+  /// # Errors
+  ///
+  /// Returns `message` pointing at the whole span if there are not enough words.
+  pub fn word(self, index: usize, message: &str) -> Result<Self, DayError> {
+    self.words().nth(index).ok_or_else(|| self.trim().error(message))
+  }
+
+  /// Exactly `N` words, e.g. `let [direction, steps] = line.fields(…)?`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `message` pointing at the whole span if there are more or fewer.
+  pub fn fields<const N: usize>(self, message: &str) -> Result<[Self; N], DayError> {
+    let words = self.words().collect::<Vec<_>>();
+    words.try_into().map_err(|_| self.trim().error(message))
+  }
+
+  /// Each character with the span it takes up.
+  pub fn chars(self) -> impl Iterator<Item = (char, Self)> + 'a {
+    self.text.char_indices().map(move |(index, c)| (c, self.to(&self.text[index..index + c.len_utf8()])))
+  }
+
+  /// Consecutive pieces of `size` characters, the last one maybe shorter, e.g.
+  /// the `[A] ` columns of a drawing.
+  pub fn chunks(self, size: usize) -> impl Iterator<Item = Self> + 'a {
+    let mut rest = self.text;
+    std::iter::from_fn(move || {
+      if rest.is_empty() || size == 0 {
+        return None;
+      }
+      let end = rest.char_indices().nth(size).map_or(rest.len(), |(index, _)| index);
+      let (chunk, after) = rest.split_at(end);
+      rest = after;
+      Some(self.to(chunk))
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn groups_between_blank_lines() {
+    let groups = groups("1\n2\n\n3\n\n\n4\n".as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+    let texts = groups.iter().map(|group| group.iter().map(Line::text).collect::<Vec<_>>()).collect::<Vec<_>>();
+    assert_eq!(texts, [vec!["1", "2"], vec!["3"], vec![], vec!["4"]]);
+    assert_eq!(groups[3][0].number(), 7);
+
+    assert_eq!(super::groups("".as_bytes()).count(), 1);
+    assert_eq!(super::groups("1\n\n".as_bytes()).count(), 2);
+  }
+
+  #[test]
+  fn errors_point_into_the_line() {
+    let line = Line::new(3, "move 1 from x to 3");
+    let [_, _, _, from, _, _] = line.span().fields("expected six words").unwrap();
+    assert_eq!(from.parse::<usize>("no such stack").unwrap_err().to_string(), "line 3, column 13: no such stack: `x`");
+
+    let error = line.span().fields::<2>("expected two words").unwrap_err();
+    assert_eq!(error.to_string(), "line 3, column 1: expected two words: `move 1 from x to 3`");
+  }
+
+  #[test]
+  fn keywords() {
+    let line = Line::new(1, "  Test: divisible by 23");
+    let rest = line.span().trim().keyword("Test: divisible by").unwrap();
+    assert_eq!(rest.parse::<u32>("not a number").unwrap(), 23);
+
+    assert_eq!(Span::new("moved 1", 1).keyword("move").unwrap_err().to_string(), "line 1, column 1: expected `move`: `moved`");
+    assert!(Span::new("noop", 1).keyword("noop").unwrap().is_empty());
+  }
+
+  #[test]
+  fn separators() {
+    let span = Span::new("2-4,6-8", 1);
+    let (first, second) = span.split_once(",", "expected two ranges").unwrap();
+    assert_eq!((first.as_str(), second.as_str()), ("2-4", "6-8"));
+    let ends = second.split("-").map(|end| end.parse::<u32>("not a number")).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(ends, [6, 8]);
+    assert_eq!(first.split_once(",", "expected two ranges").unwrap_err().to_string(), "line 1, column 1: expected two ranges: `2-4`");
+  }
+
+  #[test]
+  fn one_of_choices() {
+    let choices = [("A", 1), ("B", 2)];
+    assert_eq!(Span::new("B", 1).one_of(&choices, "invalid choice").unwrap(), 2);
+    assert_eq!(Span::new("A Q", 2).word(1, "missing").unwrap().one_of(&choices, "invalid choice").unwrap_err().to_string(), "line 2, column 3: invalid choice: `Q`");
+  }
+
+  #[test]
+  fn chars_and_chunks() {
+    let span = Span::new("[Z] [M] [P]", 1);
+    assert_eq!(span.chunks(4).map(Span::as_str).collect::<Vec<_>>(), ["[Z] ", "[M] ", "[P]"]);
+    let (_, p) = span.chars().find(|(c, _)| *c == 'P').unwrap();
+    assert_eq!(p.error("x").to_string(), "line 1, column 10: x: `P`");
+    assert_eq!(Span::new("", 1).chunks(4).count(), 0);
+  }
+}
@@ -0,0 +1,274 @@
+use std::{
+  collections::BTreeMap,
+  fmt::{self, Display},
+  fs,
+  path::{Path, PathBuf},
+};
+use serde::Deserialize;
+
+use crate::{workspace_root, Day, DayError, Solution};
+
+/// The expected answers of every known input, read from `answers.toml` at the
+/// root of the workspace:
+///
+/// ```toml
+/// [day-01]
+/// "input.txt" = { part1 = 66186, part2 = 196804 }
+/// ```
+///
+/// Inputs are paths inside the day's directory, and either part may be left out
+/// when its answer is not known yet.
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+  days: BTreeMap<String, BTreeMap<String, Expected>>,
+}
+
+/// The expected answers of one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+  pub part_one: Option<String>,
+  pub part_two: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawExpected {
+  part1: Option<RawAnswer>,
+  part2: Option<RawAnswer>,
+}
+
+// answers can be written as plain numbers or as strings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawAnswer {
+  Number(i64),
+  Text(String),
+}
+
+impl From<RawAnswer> for String {
+  fn from(answer: RawAnswer) -> Self {
+    match answer {
+      RawAnswer::Number(number) => number.to_string(),
+      RawAnswer::Text(text) => text,
+    }
+  }
+}
+
+impl Registry {
+  /// The workspace's `answers.toml`.
+  #[must_use]
+  pub fn path() -> PathBuf {
+    workspace_root().join("answers.toml")
+  }
+
+  /// Reads the workspace's `answers.toml`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the file cannot be read or is not a valid registry.
+  pub fn load() -> Result<Self, DayError> {
+    Self::from_file(&Self::path())
+  }
+
+  /// # Errors
+  ///
+  /// Returns an error if the file cannot be read or is not a valid registry.
+  pub fn from_file(path: &Path) -> Result<Self, DayError> {
+    let text = fs::read_to_string(path).map_err(|e| DayError::from(e).in_file(path))?;
+    Self::parse(&text).map_err(|e| e.in_file(path))
+  }
+
+  /// # Errors
+  ///
+  /// Returns an error pointing at the offending line if `text` is not a valid registry.
+  pub fn parse(text: &str) -> Result<Self, DayError> {
+    let raw: BTreeMap<String, BTreeMap<String, RawExpected>> = toml::from_str(text).map_err(|error| toml_error(text, &error))?;
+
+    let days = raw.into_iter().map(|(day, inputs)| {
+      let inputs = inputs.into_iter().map(|(input, expected)| {
+        (input, Expected { part_one: expected.part1.map(String::from), part_two: expected.part2.map(String::from) })
+      });
+      (day, inputs.collect())
+    });
+
+    Ok(Registry { days: days.collect() })
+  }
+
+  /// Every registered input of `day` with its expected answers, in name order.
+  pub fn inputs(&self, day: u8) -> impl Iterator<Item = (&str, &Expected)> {
+    self.days.get(&format!("day-{day:02}")).into_iter().flatten().map(|(input, expected)| (input.as_str(), expected))
+  }
+
+  /// The expected answers of one input of `day`, by its path inside the day's directory.
+  #[must_use]
+  pub fn expected(&self, day: u8, input: &str) -> Option<&Expected> {
+    self.inputs(day).find(|(name, _)| *name == input).map(|(_, expected)| expected)
+  }
+
+  /// Runs `day` against each of its registered inputs and compares the answers.
+  #[must_use]
+  pub fn check(&self, day: &Day) -> Vec<Check> {
+    self.inputs(day.number).map(|(input, expected)| {
+      let path = day.directory().join(input);
+      let outcome = if path.exists() {
+        match day.run(&path) {
+          Ok(answers) => {
+            let mismatches = [(1, &expected.part_one, answers.part_one), (2, &expected.part_two, answers.part_two)]
+              .into_iter()
+              .filter_map(|(part, expected, actual)| match expected {
+                Some(expected) if !same_answer(expected, &actual) => Some(Mismatch { part, expected: expected.clone(), actual }),
+                _ => None,
+              })
+              .collect::<Vec<_>>();
+            if mismatches.is_empty() { Outcome::Pass } else { Outcome::Mismatch(mismatches) }
+          }
+          Err(error) => Outcome::Failed(error),
+        }
+      } else {
+        Outcome::Missing
+      };
+
+      Check { day: day.number, input: input.to_string(), outcome }
+    }).collect()
+  }
+}
+
+/// Whether an answer is the expected one. Multi-line answers (day 10's CRT) are
+/// compared row by row, ignoring trailing blanks and the line breaks around them
+/// in the TOML.
+#[must_use]
+pub fn same_answer(expected: &str, actual: &str) -> bool {
+  rows(expected).eq(rows(actual))
+}
+
+fn rows(answer: &str) -> impl Iterator<Item = &str> {
+  answer.trim_matches('\n').lines().map(str::trim_end)
+}
+
+fn toml_error(text: &str, error: &toml::de::Error) -> DayError {
+  let message = error.message().to_string();
+  let Some(span) = error.span() else {
+    return DayError::parse(text, text, message).on_line(1);
+  };
+
+  let start = span.start.min(text.len());
+  let line_start = text[..start].rfind('\n').map_or(0, |newline| newline + 1);
+  let line_end = text[start..].find('\n').map_or(text.len(), |newline| start + newline);
+  let line = &text[line_start..line_end];
+  let token = &text[start..span.end.clamp(start, line_end)];
+  let number = text[..line_start].matches('\n').count() + 1;
+
+  DayError::parse(line, if token.is_empty() { line } else { token }, message).on_line(number)
+}
+
+/// The result of running one day against one registered input.
+#[derive(Debug)]
+pub struct Check {
+  pub day: u8,
+  pub input: String,
+  pub outcome: Outcome,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+  Pass,
+  Mismatch(Vec<Mismatch>),
+  Failed(DayError),
+  /// The input is registered but not on disk, e.g. someone else's input.
+  Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+  pub part: u8,
+  pub expected: String,
+  pub actual: String,
+}
+
+impl Outcome {
+  #[must_use]
+  pub fn name(&self) -> &'static str {
+    match self {
+      Outcome::Pass => "pass",
+      Outcome::Mismatch(_) => "mismatch",
+      Outcome::Failed(_) => "fail",
+      Outcome::Missing => "missing",
+    }
+  }
+
+  /// Whether this outcome should fail a run: a wrong answer or an error.
+  #[must_use]
+  pub fn is_failure(&self) -> bool {
+    matches!(self, Outcome::Mismatch(_) | Outcome::Failed(_))
+  }
+}
+
+impl Display for Outcome {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Outcome::Pass | Outcome::Missing => Ok(()),
+      Outcome::Mismatch(mismatches) => {
+        let details = mismatches.iter().map(ToString::to_string).collect::<Vec<_>>();
+        write!(f, "{}", details.join(", "))
+      }
+      Outcome::Failed(error) => write!(f, "{error}"),
+    }
+  }
+}
+
+impl Display for Mismatch {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let part = if self.part == 1 { "part one" } else { "part two" };
+    if self.expected.contains('\n') || self.actual.contains('\n') {
+      write!(f, "{part}: expected\n{}\ngot\n{}", self.expected.trim_matches('\n'), self.actual)
+    } else {
+      write!(f, "{part}: expected {}, got {}", self.expected, self.actual)
+    }
+  }
+}
+
+/// Checks `S` against every input registered for it that is on disk, for use in
+/// each day's tests.
+///
+/// # Panics
+///
+/// Panics listing every wrong answer and error, or if the day has no inputs
+/// registered at all.
+pub fn assert_registered<S: Solution>() {
+  let registry = Registry::load().unwrap_or_else(|error| panic!("{error}"));
+  let checks = registry.check(&Day::new::<S>());
+  assert!(!checks.is_empty(), "no inputs registered for day {:02} in {}", S::DAY, Registry::path().display());
+
+  let failures = checks.iter()
+    .filter(|check| check.outcome.is_failure())
+    .map(|check| format!("{}: {}: {}", check.input, check.outcome.name(), check.outcome))
+    .collect::<Vec<_>>();
+  assert!(failures.is_empty(), "day {:02}:\n{}", S::DAY, failures.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_numbers_and_strings() {
+    let registry = Registry::parse("[day-05]\n\"input.txt\" = { part1 = \"CMZ\", part2 = 12 }\n").unwrap();
+    let inputs = registry.inputs(5).collect::<Vec<_>>();
+    assert_eq!(inputs, vec![("input.txt", &Expected { part_one: Some(String::from("CMZ")), part_two: Some(String::from("12")) })]);
+    assert_eq!(registry.inputs(6).count(), 0);
+    assert_eq!(registry.expected(5, "input.txt"), Some(inputs[0].1));
+    assert_eq!(registry.expected(5, "example.txt"), None);
+  }
+
+  #[test]
+  fn parse_error_points_at_line() {
+    let error = Registry::parse("[day-01]\n\"input.txt\" = { part1 = 1, part3 = 2 }\n").unwrap_err();
+    assert!(matches!(error, DayError::Parse { line: 2, .. }), "{error}");
+  }
+
+  #[test]
+  fn multi_line_answers_ignore_trailing_blanks() {
+    assert!(same_answer("\n█ █  \n ██\n", "█ █\n ██"));
+    assert!(!same_answer("█ █\n ██", "█ █\n██"));
+  }
+}
@@ -0,0 +1,77 @@
+use std::ops::RangeInclusive;
+
+/// A small deterministic random number generator (`SplitMix64`) for building
+/// synthetic puzzle inputs. It is not suitable for anything security related,
+/// but gives the same stream for the same seed on every platform.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+  #[must_use]
+  pub fn new(seed: u64) -> Self {
+    Rng(seed)
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+
+  /// A number in `0..bound`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `bound` is zero.
+  pub fn below(&mut self, bound: usize) -> usize {
+    assert!(bound > 0, "cannot pick below zero");
+    let bound = bound as u64;
+    usize::try_from(self.next_u64() % bound).expect("value is below a usize bound")
+  }
+
+  /// A number in `range`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `range` is empty.
+  pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+    let (start, end) = range.into_inner();
+    assert!(start <= end, "cannot pick from an empty range");
+    start + self.below(end - start + 1)
+  }
+
+  /// A uniformly chosen element of `items`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `items` is empty.
+  pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+    &items[self.below(items.len())]
+  }
+
+  pub fn shuffle<T>(&mut self, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+      items.swap(i, self.below(i + 1));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn same_seed_same_stream() {
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+    assert_eq!((0..8).map(|_| a.next_u64()).collect::<Vec<_>>(), (0..8).map(|_| b.next_u64()).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn range_stays_in_bounds() {
+    let mut rng = Rng::new(7);
+    assert!((0..1000).map(|_| rng.range(3..=5)).all(|n| (3..=5).contains(&n)));
+  }
+}
@@ -0,0 +1,160 @@
+//! What the days were doing, as `tracing` spans and events written to stderr.
+//!
+//! The runner wraps every day in a `day` span with a `parse`, `part_one` or
+//! `part_two` span inside, and the days emit events at `debug` and `trace` on
+//! their own targets, e.g. every throw of day 11 on `day_11`.
+use std::io;
+
+use tracing::Subscriber;
+use tracing_subscriber::{
+  fmt::{format::FmtSpan, MakeWriter},
+  EnvFilter,
+};
+
+use crate::Format;
+
+/// A subscriber sending whatever `filter` lets through to `writer`, as text or
+/// as one JSON object per line.
+///
+/// # Errors
+///
+/// Returns an error if `filter` is not a valid filter.
+pub fn subscriber<W>(filter: &str, format: Format, writer: W) -> Result<Box<dyn Subscriber + Send + Sync>, String>
+where
+  W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+  let env_filter = EnvFilter::try_new(normalize(filter)).map_err(|error| format!("invalid trace filter `{filter}`: {error}"))?;
+  // a line when each span closes, with how long it took
+  let builder = tracing_subscriber::fmt().with_env_filter(env_filter).with_writer(writer).with_ansi(false).with_span_events(FmtSpan::CLOSE);
+
+  Ok(match format {
+    Format::Text => Box::new(builder.finish()),
+    Format::Json => Box::new(builder.json().with_current_span(true).with_span_list(true).finish()),
+  })
+}
+
+/// Sends whatever `filter` lets through to stderr for the rest of the program,
+/// e.g. `day11=debug` for every throw of day 11.
+///
+/// # Errors
+///
+/// Returns an error if `filter` is not a valid filter or tracing was already set up.
+pub fn init(filter: &str, format: Format) -> Result<(), String> {
+  tracing::subscriber::set_global_default(subscriber(filter, format, io::stderr)?).map_err(|error| error.to_string())
+}
+
+/// `filter` with every day named the way its crate is, so `day11`, `day-11` and
+/// `day_11` all mean `day_11`. The runner's own spans are always let through so
+/// every event says which day and part it came from.
+#[must_use]
+pub fn normalize(filter: &str) -> String {
+  let mut directives = filter.split(',').filter(|directive| !directive.is_empty()).map(|directive| {
+    let end = directive.find(['=', '[']).unwrap_or(directive.len());
+    match day_target(&directive[..end]) {
+      Some(target) => format!("{target}{}", &directive[end..]),
+      None => directive.to_string(),
+    }
+  }).collect::<Vec<_>>();
+  if !directives.iter().any(|directive| directive.starts_with("aoc_runner")) {
+    directives.insert(0, String::from("aoc_runner=info"));
+  }
+
+  directives.join(",")
+}
+
+fn day_target(target: &str) -> Option<String> {
+  let number = target.strip_prefix("day")?;
+  let number = number.strip_prefix(['-', '_']).unwrap_or(number);
+  if number.is_empty() || !number.bytes().all(|byte| byte.is_ascii_digit()) {
+    return None;
+  }
+
+  Some(format!("day_{:02}", number.parse::<u8>().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use super::*;
+  use crate::Solution;
+
+  #[test]
+  fn normalize_days() {
+    assert_eq!(normalize("day11=debug"), "aoc_runner=info,day_11=debug");
+    assert_eq!(normalize("day-5=trace,day_09[day]=debug"), "aoc_runner=info,day_05=trace,day_09[day]=debug");
+    assert_eq!(normalize("aoc_runner=debug,daylight=info,debug"), "aoc_runner=debug,daylight=info,debug");
+  }
+
+  #[test]
+  fn invalid_filter() {
+    let error = subscriber("day11=loud", Format::Text, io::sink).err().unwrap();
+    assert!(error.starts_with("invalid trace filter `day11=loud`"), "{error}");
+  }
+
+  // a day that reports what it parsed
+  struct Traced;
+
+  impl Solution for Traced {
+    const DAY: u8 = 11;
+
+    type Input = usize;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(reader: impl io::BufRead) -> Result<usize, crate::DayError> {
+      let lines = reader.lines().count();
+      tracing::debug!(target: "day_11", lines, "parsed");
+      Ok(lines)
+    }
+
+    fn part_one(lines: &usize) -> Result<usize, crate::DayError> {
+      Ok(*lines)
+    }
+
+    fn part_two(lines: &usize) -> Result<usize, crate::DayError> {
+      Ok(*lines)
+    }
+  }
+
+  #[derive(Clone, Default)]
+  struct Captured(Arc<Mutex<Vec<u8>>>);
+
+  impl io::Write for Captured {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+      self.0.lock().unwrap().extend_from_slice(bytes);
+      Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+      Ok(())
+    }
+  }
+
+  fn trace(filter: &str, format: Format) -> Vec<String> {
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = subscriber(filter, format, move || writer.clone()).unwrap();
+    tracing::subscriber::with_default(subscriber, || crate::measure::<Traced>(b"a\nb\n").unwrap());
+
+    let bytes = captured.0.lock().unwrap().clone();
+    String::from_utf8(bytes).unwrap().lines().map(String::from).collect()
+  }
+
+  #[test]
+  fn json_lines_inside_the_runner_spans() {
+    let lines = trace("day11=debug", Format::Json);
+    let event = lines.iter().find(|line| line.contains("\"parsed\"")).unwrap();
+    assert!(event.contains(r#""lines":2"#), "{event}");
+    assert!(event.contains(r#""target":"day_11""#), "{event}");
+    assert!(event.contains(r#"{"day":11,"name":"day"},{"name":"parse"}"#), "{event}");
+    assert!(lines.iter().any(|line| line.contains(r#""message":"close""#) && line.contains(r#""name":"part_two""#)), "{lines:?}");
+  }
+
+  #[test]
+  fn filtered_out() {
+    let lines = trace("day10=debug", Format::Text);
+    assert!(!lines.iter().any(|line| line.contains("parsed")), "{lines:?}");
+    assert!(lines.iter().any(|line| line.contains("day{day=11}:parse: aoc_runner: close")), "{lines:?}");
+  }
+}
@@ -0,0 +1,139 @@
+#![warn(clippy::all, clippy::pedantic)]
+//! Every day compiled to WebAssembly for the playground that `aoc serve` puts
+//! up: paste an input, get the answers and, for some days, a picture of how
+//! they were found. Build it with `wasm-pack build --target web aoc-wasm`.
+use aoc_runner::{Day, DayError, Solution};
+use wasm_bindgen::prelude::*;
+
+const DAYS: &[Day] = &[
+  Day::new::<day_01::Day01>(),
+  Day::new::<day_02::Day02>(),
+  Day::new::<day_03::Day03>(),
+  Day::new::<day_04::Day04>(),
+  Day::new::<day_05::Day05>(),
+  Day::new::<day_06::Day06>(),
+  Day::new::<day_07::Day07>(),
+  Day::new::<day_08::Day08>(),
+  Day::new::<day_09::Day09>(),
+  Day::new::<day_10::Day10>(),
+  Day::new::<day_11::Day11>(),
+];
+
+/// The answers for one input, and the picture of how they were found if the
+/// day has one.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solved {
+  pub part_one: String,
+  pub part_two: String,
+  pub picture: Option<String>,
+}
+
+/// The number of every day there is a solver for.
+#[wasm_bindgen]
+#[must_use]
+pub fn days() -> Vec<u8> {
+  DAYS.iter().map(|day| day.number).collect()
+}
+
+/// Solves `day` for `input`.
+///
+/// # Errors
+///
+/// Throws the error message if there is no such day, or the input is
+/// malformed or has no answer.
+#[wasm_bindgen]
+pub fn solve(day: u8, input: &str) -> Result<Solved, JsError> {
+  solve_day(day, input).map_err(|error| JsError::new(&error.to_string()))
+}
+
+fn solve_day(number: u8, input: &str) -> Result<Solved, DayError> {
+  let day = DAYS.iter().find(|day| day.number == number).ok_or_else(|| DayError::unsolvable(format!("day {number} is not solved yet")))?;
+  let (part_one, part_two) = day.solve_str(input)?;
+
+  Ok(Solved { part_one, part_two, picture: picture(number, input)? })
+}
+
+// text to show in a `<pre>`, like the pictures of `aoc tui`
+fn picture(day: u8, input: &str) -> Result<Option<String>, DayError> {
+  let picture = match day {
+    5 => stacks(input)?,
+    8 => scenic_scores(input)?,
+    9 => rope_tail(input)?,
+    10 => day_10::solve_str(input)?.1,
+    _ => return Ok(None),
+  };
+
+  Ok(Some(picture))
+}
+
+fn stacks(input: &str) -> Result<String, DayError> {
+  let plan = day_05::Day05::parse(input.as_bytes())?;
+  Ok(plan.draw_both()?.join("\n"))
+}
+
+// from ` ` for the lowest score to `@` for the highest
+fn scenic_scores(input: &str) -> Result<String, DayError> {
+  const SHADES: &[u8] = b" .:-=+*#%@";
+  let forest = day_08::Day08::parse(input.as_bytes())?;
+  let scores = forest.trees.map(|tree, _| forest.calculate_score_for_position(tree));
+  let highest = scores.iter().map(|(_, &score)| score).max().unwrap_or_default().max(1);
+
+  Ok(scores.render(|&score| {
+    let shade = (u64::from(score) * (SHADES.len() as u64 - 1)).div_ceil(u64::from(highest));
+    char::from(SHADES[usize::try_from(shade).unwrap_or(SHADES.len() - 1)])
+  }))
+}
+
+// `s` where the tail started and `#` everywhere else it went
+fn rope_tail(input: &str) -> Result<String, DayError> {
+  let motions = day_09::Day09::parse(input.as_bytes())?;
+  let trail = day_09::trail_grid(&day_09::tail_trail(&motions, 9));
+
+  Ok(trail.render(|&cell| match cell {
+    2 => 's',
+    1 => '#',
+    _ => '.',
+  }))
+}
+
+#[cfg(test)]
+mod tests {
+  use wasm_bindgen_test::*;
+
+  use super::*;
+
+  #[wasm_bindgen_test(unsupported = test)]
+  fn solve_example() {
+    let solved = solve(1, include_str!("../../day-01/example.txt")).unwrap();
+    assert_eq!((solved.part_one.as_str(), solved.part_two.as_str(), solved.picture), ("24000", "45000", None));
+  }
+
+  #[wasm_bindgen_test(unsupported = test)]
+  fn every_day() {
+    assert_eq!(days(), (1..=11).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn errors() {
+    assert_eq!(solve_day(25, "").unwrap_err().to_string(), "day 25 is not solved yet");
+    assert_eq!(solve_day(2, "A Q\n").unwrap_err().to_string(), "line 1, column 3: invalid choice: `Q`");
+  }
+
+  #[wasm_bindgen_test(unsupported = test)]
+  fn stacks_after_moving() {
+    let picture = solve(5, include_str!("../../day-05/example.txt")).unwrap().picture.unwrap();
+    assert!(picture.starts_with("CrateMover 9000\n        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n 1   2   3\n"), "{picture}");
+  }
+
+  #[wasm_bindgen_test(unsupported = test)]
+  fn scenic_score_shades() {
+    let picture = solve(8, include_str!("../../day-08/example.txt")).unwrap().picture.unwrap();
+    assert_eq!(picture.lines().nth(3), Some(" :@= "));
+  }
+
+  #[wasm_bindgen_test(unsupported = test)]
+  fn rope_tail_trail() {
+    assert_eq!(solve(9, include_str!("../../day-09/example_1.txt")).unwrap().picture.unwrap(), "s");
+  }
+}
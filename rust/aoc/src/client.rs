@@ -0,0 +1,316 @@
+use std::{
+  error::Error,
+  fmt::{self, Display},
+  fs,
+  io,
+  path::{Path, PathBuf},
+  thread,
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::config::{Config, YEAR};
+
+/// Everything that can go wrong while talking to adventofcode.com.
+#[derive(Debug)]
+pub enum ClientError {
+  Io {
+    path: PathBuf,
+    source: io::Error,
+  },
+  Config {
+    path: PathBuf,
+    message: String,
+  },
+  /// The submission history could not be parsed.
+  History {
+    path: PathBuf,
+    message: String,
+  },
+  MissingSession,
+  /// The server could not be reached at all.
+  Transport(String),
+  /// The session token was not accepted.
+  SessionRejected,
+  /// The puzzle is not unlocked yet.
+  NotUnlocked(u8),
+  Status {
+    status: u16,
+    url: String,
+  },
+  /// An answer that was not sent, and why.
+  Refused(String),
+  /// A response that does not look like any page we know.
+  UnexpectedResponse(String),
+}
+
+impl Display for ClientError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ClientError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+      ClientError::Config { path, message } | ClientError::History { path, message } => write!(f, "{}: {message}", path.display()),
+      ClientError::MissingSession => write!(f, "no session token, set `session` in the config file or `AOC_SESSION`"),
+      ClientError::Transport(message) => write!(f, "request failed: {message}"),
+      ClientError::SessionRejected => write!(f, "session token rejected, it may have expired"),
+      ClientError::NotUnlocked(day) => write!(f, "day {day} is not unlocked yet"),
+      ClientError::Status { status, url } => write!(f, "{url}: unexpected status {status}"),
+      ClientError::Refused(reason) => write!(f, "not submitted: {reason}"),
+      ClientError::UnexpectedResponse(url) => write!(f, "{url}: could not make sense of the response"),
+    }
+  }
+}
+
+impl Error for ClientError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      ClientError::Io { source, .. } => Some(source),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+  Get,
+  Post,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+  pub method: Method,
+  pub url: String,
+  pub headers: Vec<(String, String)>,
+  /// A form-encoded body for `POST`s.
+  pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+  pub status: u16,
+  pub body: String,
+}
+
+/// Sends HTTP requests. Any status the server answers with is a [`Response`];
+/// only failing to get an answer at all is an error.
+pub trait Transport {
+  /// # Errors
+  ///
+  /// Returns an error if the server cannot be reached.
+  fn send(&self, request: &Request) -> Result<Response, ClientError>;
+}
+
+/// The real transport.
+pub struct HttpTransport {
+  agent: ureq::Agent,
+}
+
+impl HttpTransport {
+  #[must_use]
+  pub fn new() -> Self {
+    let agent = ureq::AgentBuilder::new()
+      .timeout(Duration::from_secs(30))
+      .user_agent("github.com/bekapod/advent-of-code-2022 aoc runner")
+      .build();
+
+    HttpTransport { agent }
+  }
+}
+
+impl Default for HttpTransport {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Transport for HttpTransport {
+  fn send(&self, request: &Request) -> Result<Response, ClientError> {
+    let method = match request.method {
+      Method::Get => "GET",
+      Method::Post => "POST",
+    };
+    let mut call = self.agent.request(method, &request.url);
+    for (name, value) in &request.headers {
+      call = call.set(name, value);
+    }
+    let result = match &request.body {
+      Some(body) => call.set("Content-Type", "application/x-www-form-urlencoded").send_string(body),
+      None => call.call(),
+    };
+
+    let response = match result {
+      Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+      Err(ureq::Error::Transport(error)) => return Err(ClientError::Transport(error.to_string())),
+    };
+    let status = response.status();
+    let body = response.into_string().map_err(|error| ClientError::Transport(error.to_string()))?;
+
+    Ok(Response { status, body })
+  }
+}
+
+/// A puzzle input and whether it came from the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+  pub input: String,
+  pub cached: bool,
+}
+
+/// Talks to adventofcode.com (or whatever `base_url` points at) on behalf of one
+/// user, caching what it downloads and spacing requests out.
+pub struct Client<T: Transport> {
+  transport: T,
+  config: Config,
+}
+
+impl<T: Transport> Client<T> {
+  pub fn new(transport: T, config: Config) -> Self {
+    Client { transport, config }
+  }
+
+  /// The input of `day`, from the cache unless `refresh` is set.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if there is no session token, the request fails, or the
+  /// cache cannot be written.
+  pub fn input(&self, day: u8, refresh: bool) -> Result<Fetched, ClientError> {
+    let path = self.config.user_cache_dir()?.join(format!("day-{day:02}.txt"));
+    if !refresh {
+      if let Ok(input) = fs::read_to_string(&path) {
+        return Ok(Fetched { input, cached: true });
+      }
+    }
+
+    let response = self.send(Method::Get, &format!("/{YEAR}/day/{day}/input"), None)?;
+    let input = match response.status {
+      200 => response.body,
+      400 | 401 => return Err(ClientError::SessionRejected),
+      404 => return Err(ClientError::NotUnlocked(day)),
+      status => return Err(ClientError::Status { status, url: self.url(&format!("/{YEAR}/day/{day}/input")) }),
+    };
+
+    write(&path, &input)?;
+    Ok(Fetched { input, cached: false })
+  }
+
+  /// Sends an authenticated request to `path`, waiting first if the previous
+  /// request was too recent.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if there is no session token or the server cannot be reached.
+  pub fn send(&self, method: Method, path: &str, body: Option<String>) -> Result<Response, ClientError> {
+    let cookie = format!("session={}", self.config.session()?);
+    self.throttle()?;
+
+    let request = Request { method, url: self.url(path), headers: vec![(String::from("Cookie"), cookie)], body };
+    self.transport.send(&request)
+  }
+
+  #[must_use]
+  pub fn config(&self) -> &Config {
+    &self.config
+  }
+
+  fn url(&self, path: &str) -> String {
+    format!("{}{path}", self.config.base_url.trim_end_matches('/'))
+  }
+
+  // the time of the last request is kept on disk so separate runs share the limit
+  fn throttle(&self) -> Result<(), ClientError> {
+    let path = self.config.cache_dir.join("last-request");
+    let last = fs::read_to_string(&path).ok().and_then(|millis| millis.trim().parse::<u64>().ok()).map(Duration::from_millis);
+    if let Some(wait) = last.and_then(|last| wait_time(last, now(), self.config.min_interval)) {
+      thread::sleep(wait);
+    }
+
+    write(&path, &now().as_millis().to_string())
+  }
+}
+
+pub fn now() -> Duration {
+  SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+// how much longer to wait before the next request, if at all
+fn wait_time(last: Duration, now: Duration, min_interval: Duration) -> Option<Duration> {
+  (last + min_interval).checked_sub(now).filter(|wait| !wait.is_zero())
+}
+
+pub fn write(path: &Path, contents: &str) -> Result<(), ClientError> {
+  let io_error = |source| ClientError::Io { path: path.to_path_buf(), source };
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent).map_err(io_error)?;
+  }
+
+  fs::write(path, contents).map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Instant;
+
+  use super::*;
+  use crate::stand_in::StandIn;
+
+  #[test]
+  fn fetch_sends_session_and_caches() {
+    let server = StandIn::start(|request| match request.path.as_str() {
+      "/2022/day/1/input" => (200, String::from("1000\n2000\n")),
+      _ => (404, String::new()),
+    });
+    let client = Client::new(HttpTransport::new(), server.config("fetch"));
+
+    assert_eq!(client.input(1, false).unwrap(), Fetched { input: String::from("1000\n2000\n"), cached: false });
+    assert_eq!(client.input(1, false).unwrap(), Fetched { input: String::from("1000\n2000\n"), cached: true });
+    assert!(!client.input(1, true).unwrap().cached);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!((requests[0].method.as_str(), requests[0].path.as_str()), ("GET", "/2022/day/1/input"));
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+  }
+
+  #[test]
+  fn fetch_reports_locked_days_and_bad_sessions() {
+    let server = StandIn::start(|request| match request.header("cookie") {
+      Some("session=secret") => (404, String::from("Please don't repeatedly request this endpoint before it unlocks!")),
+      _ => (400, String::from("Puzzle inputs differ by user.  Please log in to get your puzzle input.")),
+    });
+    let mut config = server.config("locked");
+    let client = Client::new(HttpTransport::new(), config.clone());
+    assert!(matches!(client.input(25, false), Err(ClientError::NotUnlocked(25))));
+
+    config.session = Some(String::from("expired"));
+    let client = Client::new(HttpTransport::new(), config);
+    assert!(matches!(client.input(1, false), Err(ClientError::SessionRejected)));
+  }
+
+  #[test]
+  fn requests_are_spaced_out() {
+    let server = StandIn::start(|_| (200, String::from("input")));
+    let mut config = server.config("throttle");
+    config.min_interval = Duration::from_millis(300);
+    let client = Client::new(HttpTransport::new(), config);
+
+    let start = Instant::now();
+    client.input(1, true).unwrap();
+    client.input(1, true).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(300));
+  }
+
+  #[test]
+  fn wait_time_only_within_interval() {
+    let second = Duration::from_secs(1);
+    assert_eq!(wait_time(10 * second, 12 * second, 5 * second), Some(3 * second));
+    assert_eq!(wait_time(10 * second, 15 * second, 5 * second), None);
+    assert_eq!(wait_time(10 * second, 20 * second, 5 * second), None);
+  }
+
+  #[test]
+  fn unreachable_server_is_a_transport_error() {
+    let mut config = StandIn::start(|_| (200, String::new())).config("unreachable");
+    config.base_url = String::from("http://127.0.0.1:9");
+    let client = Client::new(HttpTransport::new(), config);
+    assert!(matches!(client.input(1, false), Err(ClientError::Transport(_))));
+  }
+}
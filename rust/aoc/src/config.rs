@@ -0,0 +1,143 @@
+use std::{
+  env,
+  fs,
+  io,
+  path::{Path, PathBuf},
+  time::Duration,
+};
+use serde::Deserialize;
+
+use crate::client::ClientError;
+
+/// Settings for talking to adventofcode.com, read from `$AOC_CONFIG`, or else
+/// `aoc/config.toml` in the user's config directory:
+///
+/// ```toml
+/// session = "53616c7465645f5f…"
+/// # optional, names the cache directory instead of a hash of the session
+/// user = "alice"
+/// # optional, seconds to wait between requests (default 5)
+/// min_interval_secs = 5
+/// # optional, for testing against something other than adventofcode.com
+/// base_url = "http://localhost:8000"
+/// cache_dir = "/tmp/aoc-cache"
+/// ```
+///
+/// `$AOC_SESSION` overrides the session token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+  pub session: Option<String>,
+  pub user: Option<String>,
+  pub base_url: String,
+  pub cache_dir: PathBuf,
+  pub min_interval: Duration,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+  session: Option<String>,
+  user: Option<String>,
+  base_url: Option<String>,
+  cache_dir: Option<PathBuf>,
+  min_interval_secs: Option<u64>,
+}
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+
+impl Config {
+  /// # Errors
+  ///
+  /// Returns an error if the config file exists but cannot be read or parsed.
+  pub fn load() -> Result<Self, ClientError> {
+    let path = env::var_os("AOC_CONFIG").map_or_else(|| user_dir("XDG_CONFIG_HOME", ".config").join("aoc/config.toml"), PathBuf::from);
+    let mut config = Self::from_file(&path)?;
+    if let Ok(session) = env::var("AOC_SESSION") {
+      config.session = Some(session);
+    }
+
+    Ok(config)
+  }
+
+  /// Reads `path`, falling back to the defaults when it does not exist.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the file cannot be read or parsed.
+  pub fn from_file(path: &Path) -> Result<Self, ClientError> {
+    let raw = match fs::read_to_string(path) {
+      Ok(text) => toml::from_str(&text).map_err(|error| ClientError::Config { path: path.to_path_buf(), message: error.message().to_string() })?,
+      Err(error) if error.kind() == io::ErrorKind::NotFound => RawConfig::default(),
+      Err(source) => return Err(ClientError::Io { path: path.to_path_buf(), source }),
+    };
+
+    Ok(Config {
+      session: raw.session.map(|session| session.trim().to_string()),
+      user: raw.user,
+      base_url: raw.base_url.unwrap_or_else(|| BASE_URL.to_string()),
+      cache_dir: raw.cache_dir.unwrap_or_else(|| user_dir("XDG_CACHE_HOME", ".cache").join("aoc")),
+      min_interval: Duration::from_secs(raw.min_interval_secs.unwrap_or(5)),
+    })
+  }
+
+  /// # Errors
+  ///
+  /// Returns an error if there is no session token.
+  pub fn session(&self) -> Result<&str, ClientError> {
+    self.session.as_deref().filter(|session| !session.is_empty()).ok_or(ClientError::MissingSession)
+  }
+
+  /// Where this user's downloads are kept, so several accounts never mix up
+  /// their inputs. Named after `user`, or else a hash of the session token.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if there is neither a user name nor a session token.
+  pub fn user_cache_dir(&self) -> Result<PathBuf, ClientError> {
+    let user = match &self.user {
+      Some(user) => user.clone(),
+      None => format!("{:016x}", fnv1a(self.session()?.as_bytes())),
+    };
+
+    Ok(self.cache_dir.join(user).join(YEAR.to_string()))
+  }
+}
+
+// `$XDG_…` if set, else the conventional directory under `$HOME`
+fn user_dir(variable: &str, fallback: &str) -> PathBuf {
+  env::var_os(variable)
+    .filter(|dir| !dir.is_empty())
+    .map_or_else(|| env::var_os("HOME").map_or_else(PathBuf::new, PathBuf::from).join(fallback), PathBuf::from)
+}
+
+// stable across Rust versions, unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+  bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn missing_file_gives_defaults() {
+    let config = Config::from_file(Path::new("does/not/exist.toml")).unwrap();
+    assert_eq!(config.base_url, BASE_URL);
+    assert_eq!(config.min_interval, Duration::from_secs(5));
+    assert!(matches!(config.session(), Err(ClientError::MissingSession)));
+  }
+
+  #[test]
+  fn cache_dir_is_per_user() {
+    let mut config = Config::from_file(Path::new("does/not/exist.toml")).unwrap();
+    config.cache_dir = PathBuf::from("cache");
+    config.session = Some(String::from("abc"));
+    let hashed = config.user_cache_dir().unwrap();
+    config.session = Some(String::from("def"));
+    assert_ne!(config.user_cache_dir().unwrap(), hashed);
+
+    config.user = Some(String::from("alice"));
+    assert_eq!(config.user_cache_dir().unwrap(), Path::new("cache/alice/2022"));
+  }
+}
@@ -0,0 +1,573 @@
+#![warn(clippy::all, clippy::pedantic)]
+mod client;
+mod config;
+mod parity;
+mod scaffold;
+mod serve;
+#[cfg(test)]
+mod stand_in;
+mod submit;
+mod tui;
+mod watch;
+
+use std::{
+  fs,
+  io::{self, Write},
+  net::TcpListener,
+  path::PathBuf,
+  process,
+};
+
+use aoc_runner::{batch, registry::Registry, workspace_root, Day, Format};
+use clap::{Args, Parser, Subcommand};
+
+use crate::{
+  client::{Client, HttpTransport},
+  config::Config,
+  parity::GoSolver,
+  submit::Verdict,
+};
+
+#[global_allocator]
+static ALLOCATOR: aoc_runner::alloc::CountingAllocator = aoc_runner::alloc::CountingAllocator;
+
+const DAYS: &[Day] = &[
+  Day::new::<day_01::Day01>().with_generator(day_01::generator::generate),
+  Day::new::<day_02::Day02>().with_generator(day_02::generator::generate),
+  Day::new::<day_03::Day03>().with_generator(day_03::generator::generate),
+  Day::new::<day_04::Day04>().with_generator(day_04::generator::generate),
+  Day::new::<day_05::Day05>().with_generator(day_05::generator::generate),
+  Day::new::<day_06::Day06>().with_generator(day_06::generator::generate),
+  Day::new::<day_07::Day07>().with_generator(day_07::generator::generate),
+  Day::new::<day_08::Day08>().with_generator(day_08::generator::generate),
+  Day::new::<day_09::Day09>().with_generator(day_09::generator::generate),
+  Day::new::<day_10::Day10>().with_generator(day_10::generator::generate),
+  Day::new::<day_11::Day11>().with_generator(day_11::generator::generate),
+];
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Advent of Code 2022 runner")]
+struct Cli {
+  #[command(subcommand)]
+  command: Command,
+
+  /// Write what the solvers do to stderr, e.g. `day11=debug` or `day05=debug,day09=trace`
+  #[arg(long, global = true, value_name = "FILTER")]
+  trace: Option<String>,
+
+  /// `text`, or `json` for one JSON object per trace line
+  #[arg(long, global = true, default_value = "text")]
+  trace_format: Format,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+  /// Solve one day, or every day with `--all`
+  Run(RunArgs),
+  /// Solve one day for many inputs in parallel and report them in one table
+  Batch(BatchArgs),
+  /// Print a synthetic input for a day
+  Gen(GenArgs),
+  /// Check every registered input against its expected answers
+  Verify(VerifyArgs),
+  /// Download a day's input into its directory
+  Fetch(FetchArgs),
+  /// Solve a day and send one part's answer to adventofcode.com
+  Submit(SubmitArgs),
+  /// Re-run a day's example tests and solver whenever its code or input changes
+  Watch(WatchArgs),
+  /// Compare the Go and Rust solvers on the fixtures and generated inputs
+  Parity(ParityArgs),
+  /// Create a day's crate from the template and register it everywhere
+  New(NewArgs),
+  /// Browse every day's status, answers and timings in the terminal
+  Tui,
+  /// Serve the WebAssembly playground to paste inputs into on localhost
+  Serve(ServeArgs),
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+  /// Day to run
+  #[arg(short, long, required_unless_present = "all", conflicts_with = "all")]
+  day: Option<u8>,
+
+  /// Run every day, resolving the input inside each day's directory
+  #[arg(short, long)]
+  all: bool,
+
+  /// Input file (`-` for stdin), defaults to the day's `input.txt`
+  input: Option<PathBuf>,
+
+  /// `text`, or `json` for one JSON object per day
+  #[arg(short, long, default_value = "text")]
+  format: Format,
+
+  /// Solve each input this many times and report min/median/max timings
+  #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+  repeat: u32,
+}
+
+#[derive(Debug, Args)]
+struct BatchArgs {
+  /// Day to run
+  #[arg(short, long)]
+  day: u8,
+
+  /// Input files, directories of them or glob patterns like `inputs/*.txt`
+  #[arg(required = true)]
+  inputs: Vec<String>,
+
+  /// `text` for a table, or `json` for one JSON object per input
+  #[arg(short, long, default_value = "text")]
+  format: Format,
+
+  /// Solve each input this many times and report the median timings
+  #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+  repeat: u32,
+
+  /// How many inputs to solve at once, defaults to the number of CPUs
+  #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+  jobs: Option<u32>,
+}
+
+#[derive(Debug, Args)]
+struct GenArgs {
+  /// Day to generate an input for
+  #[arg(short, long)]
+  day: u8,
+
+  /// Seed for the generator, defaults to the day number like the benchmarks
+  #[arg(short, long)]
+  seed: Option<u64>,
+
+  /// Size relative to a real puzzle input
+  #[arg(long, default_value_t = 1)]
+  scale: usize,
+}
+
+#[derive(Debug, Args)]
+struct VerifyArgs {
+  /// Only verify this day
+  #[arg(short, long)]
+  day: Option<u8>,
+
+  /// Answers file to use instead of the workspace's `answers.toml`
+  #[arg(long)]
+  answers: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct FetchArgs {
+  /// Day to download the input of
+  #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+  day: u8,
+
+  /// Where to save the input, defaults to the day's `input.txt`
+  #[arg(short, long)]
+  output: Option<PathBuf>,
+
+  /// Download again even if the input is cached
+  #[arg(long)]
+  refresh: bool,
+
+  /// Overwrite an existing, different input
+  #[arg(long)]
+  force: bool,
+}
+
+#[derive(Debug, Args)]
+struct SubmitArgs {
+  /// Day to submit
+  #[arg(short, long)]
+  day: u8,
+
+  /// Part to submit
+  #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+  part: u8,
+
+  /// Input file (`-` for stdin), defaults to the day's `input.txt`
+  input: Option<PathBuf>,
+
+  /// Send this answer instead of solving, e.g. the letters on day 10's CRT
+  #[arg(long, conflicts_with = "input")]
+  answer: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct WatchArgs {
+  /// Day to watch
+  #[arg(short, long)]
+  day: u8,
+
+  /// Input file to solve, defaults to the day's `input.txt`
+  input: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct ParityArgs {
+  /// Only compare this day
+  #[arg(short, long)]
+  day: Option<u8>,
+
+  /// How many generated inputs to compare on top of the fixtures
+  #[arg(long, default_value_t = 5)]
+  generated: u64,
+
+  /// Size of the generated inputs relative to a real puzzle input
+  #[arg(long, default_value_t = 1)]
+  scale: usize,
+}
+
+#[derive(Debug, Args)]
+struct NewArgs {
+  /// Day to create
+  #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+  day: u8,
+}
+
+#[derive(Debug, Args)]
+struct ServeArgs {
+  /// Port to listen on
+  #[arg(short, long, default_value_t = 8022)]
+  port: u16,
+}
+
+fn find_day(number: u8) -> &'static Day {
+  DAYS.iter().find(|day| day.number == number).unwrap_or_else(|| {
+    eprintln!("day {number} is not solved yet");
+    process::exit(2);
+  })
+}
+
+fn run(args: &RunArgs) -> bool {
+  let mut all_solved = true;
+  let days: Vec<&Day> = match args.day {
+    Some(number) => vec![find_day(number)],
+    None => DAYS.iter().collect(),
+  };
+
+  for day in days {
+    let input = match (&args.input, args.all) {
+      (Some(input), false) => input.clone(),
+      (Some(input), true) => day.directory().join(input),
+      (None, _) => day.directory().join("input.txt"),
+    };
+
+    match day.run_repeated(&input, args.repeat as usize) {
+      Ok(answers) => println!("{}", args.format.render(&answers)),
+      Err(error) => {
+        eprintln!("day {:02}: error: {error}", day.number);
+        all_solved = false;
+      }
+    }
+  }
+
+  all_solved
+}
+
+fn batch(args: &BatchArgs) -> bool {
+  let day = find_day(args.day);
+  let inputs = match batch::inputs(&args.inputs) {
+    Ok(inputs) => inputs,
+    Err(error) => {
+      eprintln!("day {:02}: error: {error}", day.number);
+      return false;
+    }
+  };
+
+  let jobs = args.jobs.map_or_else(batch::default_jobs, |jobs| jobs as usize);
+  let rows = batch::run(&inputs, jobs, |input| day.run_repeated(input, args.repeat as usize));
+  println!("{}", batch::report(&rows, args.format));
+
+  rows.iter().all(|row| row.result.is_ok())
+}
+
+fn generate(args: &GenArgs) -> bool {
+  let day = find_day(args.day);
+  let Some(input) = day.generate(args.seed.unwrap_or(u64::from(day.number)), args.scale) else {
+    eprintln!("day {:02}: error: no generator", day.number);
+    return false;
+  };
+
+  match io::stdout().lock().write_all(input.as_bytes()) {
+    Ok(()) => true,
+    // e.g. piped into `head`
+    Err(error) if error.kind() == io::ErrorKind::BrokenPipe => true,
+    Err(error) => {
+      eprintln!("day {:02}: error: {error}", day.number);
+      false
+    }
+  }
+}
+
+fn verify(args: &VerifyArgs) -> bool {
+  let path = args.answers.clone().unwrap_or_else(Registry::path);
+  let registry = match Registry::from_file(&path) {
+    Ok(registry) => registry,
+    Err(error) => {
+      eprintln!("error: {error}");
+      return false;
+    }
+  };
+  let days: Vec<&Day> = match args.day {
+    Some(number) => vec![find_day(number)],
+    None => DAYS.iter().collect(),
+  };
+
+  let checks = days.into_iter().flat_map(|day| registry.check(day)).collect::<Vec<_>>();
+  let width = checks.iter().map(|check| check.input.len()).chain(["input".len()]).max().unwrap_or_default();
+  println!("day  {:width$}  result", "input");
+  for check in &checks {
+    let detail = check.outcome.to_string().replace('\n', &format!("\n{:1$}", "", width + 17));
+    let row = format!("{:02}   {:width$}  {:8}  {detail}", check.day, check.input, check.outcome.name());
+    println!("{}", row.trim_end());
+  }
+
+  let failures = checks.iter().filter(|check| check.outcome.is_failure()).count();
+  println!("\n{} inputs, {failures} failed", checks.len());
+
+  failures == 0
+}
+
+fn fetch(args: &FetchArgs) -> bool {
+  let output = args.output.clone().unwrap_or_else(|| workspace_root().join(format!("day-{:02}", args.day)).join("input.txt"));
+  let fetched = Config::load().and_then(|config| Client::new(HttpTransport::new(), config).input(args.day, args.refresh));
+  let fetched = match fetched {
+    Ok(fetched) => fetched,
+    Err(error) => {
+      eprintln!("day {:02}: error: {error}", args.day);
+      return false;
+    }
+  };
+  let source = if fetched.cached { "cached" } else { "downloaded" };
+
+  match fs::read_to_string(&output) {
+    Ok(existing) if existing == fetched.input => {
+      println!("day {:02}: {} is up to date", args.day, output.display());
+      return true;
+    }
+    Ok(_) if !args.force => {
+      eprintln!("day {:02}: error: {} already exists and differs, pass --force to overwrite it", args.day, output.display());
+      return false;
+    }
+    _ => {}
+  }
+
+  match fs::write(&output, &fetched.input) {
+    Ok(()) => {
+      println!("day {:02}: saved {} ({source})", args.day, output.display());
+      true
+    }
+    Err(error) => {
+      eprintln!("day {:02}: error: {}: {error}", args.day, output.display());
+      false
+    }
+  }
+}
+
+fn submit(args: &SubmitArgs) -> bool {
+  let day = find_day(args.day);
+  let answer = if let Some(answer) = &args.answer {
+    answer.clone()
+  } else {
+    let input = args.input.clone().unwrap_or_else(|| day.directory().join("input.txt"));
+    match day.run(&input) {
+      Ok(answers) if args.part == 1 => answers.part_one,
+      Ok(answers) => answers.part_two,
+      Err(error) => {
+        eprintln!("day {:02}: error: {error}", day.number);
+        return false;
+      }
+    }
+  };
+  if answer.contains('\n') {
+    eprintln!("day {:02}: error: part {} is a drawing, read it and pass the letters with --answer:\n{answer}", day.number, args.part);
+    return false;
+  }
+
+  let verdict = Config::load().and_then(|config| submit::submit(&Client::new(HttpTransport::new(), config), day.number, args.part, &answer));
+  match verdict {
+    Ok(verdict) => {
+      println!("day {:02} part {}: {answer}: {verdict}", day.number, args.part);
+      matches!(verdict, Verdict::Right | Verdict::AlreadySolved)
+    }
+    Err(error) => {
+      eprintln!("day {:02} part {}: {answer}: error: {error}", day.number, args.part);
+      false
+    }
+  }
+}
+
+fn watch(args: &WatchArgs) -> bool {
+  let day = find_day(args.day);
+  let input = args.input.clone().unwrap_or_else(|| day.directory().join("input.txt"));
+  match watch::watch(day, &input) {
+    Ok(()) => true,
+    Err(error) => {
+      eprintln!("day {:02}: error: {error}", day.number);
+      false
+    }
+  }
+}
+
+fn parity(args: &ParityArgs) -> bool {
+  let go_root = parity::go_root();
+  let days = parity::go_days(&go_root).into_iter().filter(|day| args.day.is_none_or(|only| only == *day)).collect::<Vec<_>>();
+  if days.is_empty() {
+    eprintln!("error: no Go solvers to compare in {}", go_root.display());
+    return false;
+  }
+  let scratch = std::env::temp_dir().join(format!("aoc-parity-{}", process::id()));
+  if let Err(error) = fs::create_dir_all(&scratch) {
+    eprintln!("error: {}: {error}", scratch.display());
+    return false;
+  }
+
+  let mut all_built = true;
+  let mut rows = vec![];
+  for number in days {
+    let day = find_day(number);
+    let go_directory = go_root.join(format!("day-{number:02}"));
+    let solver = match GoSolver::build(&go_directory, &scratch) {
+      Ok(solver) => solver,
+      Err(error) => {
+        eprintln!("day {number:02}: error: {error}");
+        all_built = false;
+        continue;
+      }
+    };
+
+    let mut inputs = parity::fixtures(day, &go_directory);
+    for seed in 0..args.generated {
+      let Some(input) = day.generate(seed, args.scale) else { break };
+      let path = scratch.join(format!("day-{number:02}-seed-{seed}.txt"));
+      match fs::write(&path, input) {
+        Ok(()) => inputs.push((format!("seed {seed}"), path)),
+        Err(error) => eprintln!("day {number:02}: error: {}: {error}", path.display()),
+      }
+    }
+
+    for (name, path) in inputs {
+      let (rust, go) = (parity::run_rust(day, &path), solver.run(&path));
+      let divergences = parity::divergences(&rust, &go);
+      rows.push((number, name, rust, go, divergences));
+    }
+  }
+  let _ = fs::remove_dir_all(&scratch);
+  if rows.is_empty() {
+    return false;
+  }
+
+  let time = |outcome: &parity::Outcome| outcome.as_ref().map_or_else(|_| String::from("fail"), |solved| format!("{:.2?}", solved.time));
+  let width = rows.iter().map(|row| row.1.len()).chain(["input".len()]).max().unwrap_or_default();
+  println!("day  {:width$}  result   {:10}  go", "input", "rust");
+  for (number, name, rust, go, divergences) in &rows {
+    let result = if divergences.is_empty() { "same" } else { "diverge" };
+    println!("{number:02}   {name:width$}  {result:7}  {:10}  {}", time(rust), time(go));
+    for divergence in divergences {
+      println!("     {divergence}");
+    }
+  }
+
+  let diverged = rows.iter().filter(|row| !row.4.is_empty()).count();
+  println!("\n{} inputs, {diverged} diverged", rows.len());
+
+  all_built && diverged == 0
+}
+
+fn new(args: &NewArgs) -> bool {
+  match scaffold::scaffold(&workspace_root(), args.day) {
+    Ok(touched) => {
+      for path in touched {
+        println!("day {:02}: wrote {}", args.day, path.display());
+      }
+      println!("day {:02}: fill in day-{:02}/example.txt and its answers in answers.toml, then `aoc fetch --day {}`", args.day, args.day, args.day);
+      true
+    }
+    Err(error) => {
+      eprintln!("day {:02}: error: {error}", args.day);
+      false
+    }
+  }
+}
+
+fn tui() -> bool {
+  match tui::run(DAYS) {
+    Ok(()) => true,
+    Err(error) => {
+      eprintln!("error: {error}");
+      false
+    }
+  }
+}
+
+fn serve(args: &ServeArgs) -> bool {
+  let root = serve::playground_root();
+  if !root.join("pkg").is_dir() {
+    eprintln!("warning: the solvers are not built yet, run `{}` in {}", serve::BUILD, workspace_root().display());
+  }
+  let listener = match TcpListener::bind(("127.0.0.1", args.port)) {
+    Ok(listener) => listener,
+    Err(error) => {
+      eprintln!("error: cannot listen on port {}: {error}", args.port);
+      return false;
+    }
+  };
+
+  println!("playground at http://127.0.0.1:{}/", args.port);
+  match serve::serve(&listener, &root) {
+    Ok(()) => true,
+    Err(error) => {
+      eprintln!("error: {error}");
+      false
+    }
+  }
+}
+
+fn main() {
+  let cli = Cli::parse();
+  if let Some(filter) = &cli.trace {
+    if let Err(error) = aoc_runner::trace::init(filter, cli.trace_format) {
+      eprintln!("error: {error}");
+      process::exit(2);
+    }
+  }
+
+  let succeeded = match cli.command {
+    Command::Run(args) => run(&args),
+    Command::Batch(args) => batch(&args),
+    Command::Gen(args) => generate(&args),
+    Command::Verify(args) => verify(&args),
+    Command::Fetch(args) => fetch(&args),
+    Command::Submit(args) => submit(&args),
+    Command::Watch(args) => watch(&args),
+    Command::Parity(args) => parity(&args),
+    Command::New(args) => new(&args),
+    Command::Tui => tui(),
+    Command::Serve(args) => serve(&args),
+  };
+
+  if !succeeded {
+    process::exit(1);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // every day's generator gives the same input for the same seed, and one that solves
+  #[test]
+  fn generated_inputs_repeat_and_solve() {
+    for day in DAYS {
+      for seed in 0..5 {
+        let input = day.generate(seed, 1).unwrap_or_else(|| panic!("day {}: no generator", day.number));
+        assert_eq!(day.generate(seed, 1).as_deref(), Some(input.as_str()), "day {}, seed {seed}", day.number);
+        if let Err(error) = day.solve_str(&input) {
+          panic!("day {}, seed {seed}: {error}", day.number);
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,235 @@
+use std::{
+  error::Error,
+  fmt::{self, Display},
+  fs,
+  io,
+  path::{Path, PathBuf},
+  process::Command,
+  time::Duration,
+};
+
+use aoc_runner::{registry::same_answer, workspace_root, Day};
+
+/// Everything that stops the Go solvers from being compared at all.
+#[derive(Debug)]
+pub enum ParityError {
+  /// `go` could not be started.
+  MissingGo(io::Error),
+  /// `go build` failed, with what it printed.
+  Build {
+    directory: PathBuf,
+    output: String,
+  },
+}
+
+impl Display for ParityError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParityError::MissingGo(source) => write!(f, "cannot run `go`, is it installed? {source}"),
+      ParityError::Build { directory, output } => write!(f, "{}: go build failed:\n{output}", directory.display()),
+    }
+  }
+}
+
+impl Error for ParityError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      ParityError::MissingGo(source) => Some(source),
+      ParityError::Build { .. } => None,
+    }
+  }
+}
+
+/// The Go solvers next to the Rust workspace.
+#[must_use]
+pub fn go_root() -> PathBuf {
+  workspace_root().parent().map_or_else(|| PathBuf::from("go"), |root| root.join("go"))
+}
+
+/// The days `root` has a Go solver for, in order.
+#[must_use]
+pub fn go_days(root: &Path) -> Vec<u8> {
+  let mut days = fs::read_dir(root).into_iter().flatten().filter_map(|entry| {
+    let entry = entry.ok()?;
+    let day = entry.file_name().to_str()?.strip_prefix("day-")?.parse::<u8>().ok()?;
+    entry.path().join("go.mod").exists().then_some(day)
+  }).collect::<Vec<_>>();
+  days.sort_unstable();
+
+  days
+}
+
+/// The answers of one run, or why there are none.
+pub type Outcome = Result<Solved, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solved {
+  pub part_one: String,
+  pub part_two: String,
+  pub time: Duration,
+}
+
+/// A built Go solver: `day-NN <input>` printing `(part one, part two) time: Nµs`.
+#[derive(Debug)]
+pub struct GoSolver {
+  binary: PathBuf,
+}
+
+impl GoSolver {
+  /// Builds the Go module in `directory` into `out_dir`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `go` is missing or the build fails.
+  pub fn build(directory: &Path, out_dir: &Path) -> Result<Self, ParityError> {
+    let name = directory.file_name().unwrap_or(directory.as_os_str());
+    let binary = out_dir.join(name);
+    let output = Command::new("go").arg("build").arg("-o").arg(&binary).arg(".").current_dir(directory).output().map_err(ParityError::MissingGo)?;
+    if !output.status.success() {
+      return Err(ParityError::Build { directory: directory.to_path_buf(), output: String::from_utf8_lossy(&output.stderr).into_owned() });
+    }
+
+    Ok(GoSolver { binary })
+  }
+
+  /// A solver that is already built.
+  #[cfg(test)]
+  pub fn at(binary: PathBuf) -> Self {
+    GoSolver { binary }
+  }
+
+  /// Solves `input`. The Go solvers panic on inputs they cannot handle, so a
+  /// failure is the first line of whatever they printed.
+  pub fn run(&self, input: &Path) -> Outcome {
+    let output = Command::new(&self.binary).arg(input).output().map_err(|error| format!("{}: {error}", self.binary.display()))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(stderr.lines().next().unwrap_or("exited with an error").to_string());
+    }
+
+    parse_go_output(&stdout).ok_or_else(|| format!("unexpected output: {}", stdout.trim_end()))
+  }
+}
+
+/// Reads `(24000, 45000) time: 12µs`.
+#[must_use]
+pub fn parse_go_output(stdout: &str) -> Option<Solved> {
+  let (answers, time) = stdout.trim().strip_prefix('(')?.split_once(") time: ")?;
+  let (part_one, part_two) = answers.split_once(", ")?;
+  let micros = time.strip_suffix("µs")?.parse().ok()?;
+
+  Some(Solved { part_one: part_one.to_string(), part_two: part_two.to_string(), time: Duration::from_micros(micros) })
+}
+
+/// Solves `input` with the Rust solver of `day`.
+pub fn run_rust(day: &Day, input: &Path) -> Outcome {
+  let answers = day.run(input).map_err(|error| error.to_string())?;
+  Ok(Solved { part_one: answers.part_one, part_two: answers.part_two, time: answers.timings.total() })
+}
+
+/// How the two solvers disagree on one input, empty if they agree. Both
+/// rejecting an input counts as agreeing, whatever they say about it.
+#[must_use]
+pub fn divergences(rust: &Outcome, go: &Outcome) -> Vec<String> {
+  match (rust, go) {
+    (Ok(rust), Ok(go)) => [("part one", &rust.part_one, &go.part_one), ("part two", &rust.part_two, &go.part_two)]
+      .into_iter()
+      .filter(|(_, rust, go)| !same_answer(rust, go))
+      .map(|(part, rust, go)| format!("{part}: rust {rust}, go {go}"))
+      .collect(),
+    (Ok(rust), Err(error)) => vec![format!("rust answered ({}, {}), go failed: {error}", rust.part_one, rust.part_two)],
+    (Err(error), Ok(go)) => vec![format!("go answered ({}, {}), rust failed: {error}", go.part_one, go.part_two)],
+    (Err(_), Err(_)) => vec![],
+  }
+}
+
+/// The fixtures of `day` in its Rust directory, and any the Go directory has on
+/// top, by name.
+#[must_use]
+pub fn fixtures(day: &Day, go_directory: &Path) -> Vec<(String, PathBuf)> {
+  let text_files = |directory: &Path| {
+    let mut files = fs::read_dir(directory).into_iter().flatten().filter_map(|entry| {
+      let path = entry.ok()?.path();
+      let name = path.file_name()?.to_str()?.to_string();
+      (path.extension()? == "txt").then_some((name, path))
+    }).collect::<Vec<_>>();
+    files.sort();
+    files
+  };
+
+  let mut fixtures = text_files(&day.directory());
+  let extra = text_files(go_directory).into_iter().filter(|(name, _)| !fixtures.iter().any(|(rust_name, _)| rust_name == name)).collect::<Vec<_>>();
+  fixtures.extend(extra.into_iter().map(|(name, path)| (format!("go/{name}"), path)));
+
+  fixtures
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn solved(part_one: &str, part_two: &str) -> Solved {
+    Solved { part_one: part_one.to_string(), part_two: part_two.to_string(), time: Duration::ZERO }
+  }
+
+  #[test]
+  fn go_output() {
+    assert_eq!(
+      parse_go_output("(24000, 45000) time: 12µs\n"),
+      Some(Solved { part_one: String::from("24000"), part_two: String::from("45000"), time: Duration::from_micros(12) })
+    );
+    assert_eq!(parse_go_output("24000 45000\n"), None);
+  }
+
+  #[test]
+  fn agreeing_solvers() {
+    assert!(divergences(&Ok(solved("15", "12")), &Ok(solved("15", "12"))).is_empty());
+    assert!(divergences(&Err(String::from("invalid choice")), &Err(String::from("panic: index out of range"))).is_empty());
+  }
+
+  #[test]
+  fn diverging_solvers() {
+    assert_eq!(divergences(&Ok(solved("15", "12")), &Ok(solved("15", "13"))), vec![String::from("part two: rust 12, go 13")]);
+    assert_eq!(
+      divergences(&Ok(solved("15", "12")), &Err(String::from("panic: boom"))),
+      vec![String::from("rust answered (15, 12), go failed: panic: boom")]
+    );
+  }
+
+  #[test]
+  fn go_days_with_a_module() {
+    let root = std::env::temp_dir().join(format!("aoc-test-parity-days-{}", std::process::id()));
+    for (directory, module) in [("day-02", true), ("day-01", true), ("day-03", false), ("notes", true)] {
+      fs::create_dir_all(root.join(directory)).unwrap();
+      if module {
+        fs::write(root.join(directory).join("go.mod"), "module x\n").unwrap();
+      }
+    }
+
+    assert_eq!(go_days(&root), vec![1, 2]);
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn run_built_solver() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let directory = std::env::temp_dir().join(format!("aoc-test-parity-run-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let script = |name: &str, body: &str| {
+      let path = directory.join(name);
+      fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+      fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+      GoSolver::at(path)
+    };
+
+    let solver = script("day-01", "echo \"(24000, 45000) time: 7µs\"");
+    assert_eq!(solver.run(Path::new("example.txt")).unwrap().part_two, "45000");
+    let panicking = script("day-02", "echo 'panic: runtime error: index out of range' >&2\nexit 2");
+    assert_eq!(panicking.run(Path::new("example.txt")), Err(String::from("panic: runtime error: index out of range")));
+
+    fs::remove_dir_all(directory).unwrap();
+  }
+}
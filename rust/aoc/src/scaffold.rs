@@ -0,0 +1,278 @@
+use std::{
+  error::Error,
+  fmt::{self, Display, Write},
+  fs,
+  io,
+  path::{Path, PathBuf},
+};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.template");
+const LIB_RS: &str = include_str!("../templates/lib.rs.template");
+const MAIN_RS: &str = include_str!("../templates/main.rs.template");
+
+/// Everything that can go wrong while creating a day.
+#[derive(Debug)]
+pub enum ScaffoldError {
+  /// The day's directory is already there, nothing was touched.
+  Exists(PathBuf),
+  Io {
+    path: PathBuf,
+    source: io::Error,
+  },
+  /// A file that has to be edited does not look the way it should.
+  Malformed {
+    path: PathBuf,
+    message: String,
+  },
+}
+
+impl Display for ScaffoldError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+      ScaffoldError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+      ScaffoldError::Malformed { path, message } => write!(f, "{}: {message}", path.display()),
+    }
+  }
+}
+
+impl Error for ScaffoldError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      ScaffoldError::Io { source, .. } => Some(source),
+      _ => None,
+    }
+  }
+}
+
+/// Creates `day-NN` inside the workspace at `root` from the templates, and
+/// registers it as a workspace member, as a day of the `aoc` runner and of the
+/// WebAssembly playground, and in `answers.toml`. Refuses to touch anything if the day's directory exists.
+/// Returns every file created or edited, relative to `root`.
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+  let name = format!("day-{day:02}");
+  let directory = root.join(&name);
+  if directory.exists() {
+    return Err(ScaffoldError::Exists(directory));
+  }
+
+  // work out every edit before writing anything, so a malformed file leaves the tree as it was
+  let edits = [
+    (PathBuf::from("Cargo.toml"), add_member as fn(&str, u8) -> Result<Option<String>, String>),
+    (Path::new("aoc").join("Cargo.toml"), add_dependency),
+    (Path::new("aoc").join("src").join("main.rs"), add_day),
+    (Path::new("aoc-wasm").join("Cargo.toml"), add_dependency),
+    (Path::new("aoc-wasm").join("src").join("lib.rs"), add_day),
+    (PathBuf::from("answers.toml"), |answers, day| Ok(add_section(answers, day))),
+  ];
+  let mut edited = vec![];
+  for (path, edit) in edits {
+    let full_path = root.join(&path);
+    let contents = fs::read_to_string(&full_path).map_err(|source| ScaffoldError::Io { path: full_path.clone(), source })?;
+    if let Some(contents) = edit(&contents, day).map_err(|message| ScaffoldError::Malformed { path: full_path.clone(), message })? {
+      edited.push((path, contents));
+    }
+  }
+
+  let files = [
+    (Path::new(&name).join("Cargo.toml"), render(CARGO_TOML, day)),
+    (Path::new(&name).join("src").join("lib.rs"), render(LIB_RS, day)),
+    (Path::new(&name).join("src").join("main.rs"), render(MAIN_RS, day)),
+    (Path::new(&name).join("example.txt"), String::new()),
+  ];
+  let mut touched = vec![];
+  for (path, contents) in files.into_iter().chain(edited) {
+    let full_path = root.join(&path);
+    let written = match full_path.parent() {
+      Some(parent) => fs::create_dir_all(parent).and_then(|()| fs::write(&full_path, contents)),
+      None => fs::write(&full_path, contents),
+    };
+    written.map_err(|source| ScaffoldError::Io { path: full_path, source })?;
+    touched.push(path);
+  }
+
+  Ok(touched)
+}
+
+fn render(template: &str, day: u8) -> String {
+  template.replace("{{nn}}", &format!("{day:02}")).replace("{{n}}", &day.to_string())
+}
+
+// the entries between the brackets of `members = [ … ]`, one per line and sorted
+fn add_member(manifest: &str, day: u8) -> Result<Option<String>, String> {
+  let member = format!("day-{day:02}");
+  let start = manifest.find("members = [").ok_or("no workspace members")? + "members = [".len();
+  let end = start + manifest[start..].find(']').ok_or("unterminated workspace members")?;
+
+  let mut members = manifest[start..end]
+    .split(',')
+    .map(|member| member.trim().trim_matches('"'))
+    .filter(|member| !member.is_empty())
+    .collect::<Vec<_>>();
+  if members.contains(&member.as_str()) {
+    return Ok(None);
+  }
+  members.push(&member);
+  members.sort_unstable();
+
+  let mut edited = manifest[..start].to_string();
+  for member in members {
+    write!(edited, "\n  \"{member}\",").unwrap();
+  }
+  Ok(Some(format!("{edited}\n{}", &manifest[end..])))
+}
+
+// a path dependency in the sorted `[dependencies]` table of the runner or the playground
+fn add_dependency(manifest: &str, day: u8) -> Result<Option<String>, String> {
+  let name = format!("day-{day:02}");
+  let line = format!("{name} = {{ path = \"../{name}\" }}");
+  insert_sorted(manifest, "[dependencies]", |line| line.is_empty() || line.starts_with('['), &line, &format!("{name} "))
+    .map_err(|()| String::from("no [dependencies] table"))
+}
+
+// an entry in the `DAYS` table of the runner or the playground
+fn add_day(source: &str, day: u8) -> Result<Option<String>, String> {
+  let line = format!("  Day::new::<day_{day:02}::Day{day:02}>(),");
+  insert_sorted(source, "const DAYS: &[Day] = &[", |line| line == "];", &line, &format!("day_{day:02}::"))
+    .map_err(|()| String::from("no DAYS table"))
+}
+
+// inserts `new_line` among the lines after `header` up to the first one that
+// ends the block, keeping them sorted, unless a line already contains `marker`
+fn insert_sorted(text: &str, header: &str, ends: impl Fn(&str) -> bool, new_line: &str, marker: &str) -> Result<Option<String>, ()> {
+  let mut lines = text.lines().collect::<Vec<_>>();
+  let start = lines.iter().position(|line| *line == header).ok_or(())? + 1;
+  let end = start + lines[start..].iter().position(|line| ends(line)).unwrap_or(lines.len() - start);
+  if lines[start..end].iter().any(|line| line.contains(marker)) {
+    return Ok(None);
+  }
+
+  let at = start + lines[start..end].iter().position(|line| *line > new_line).unwrap_or(end - start);
+  lines.insert(at, new_line);
+  Ok(Some(lines.join("\n") + "\n"))
+}
+
+// an empty section before the first later day, so every input is checked once it is on disk
+fn add_section(answers: &str, day: u8) -> Option<String> {
+  let header = format!("[day-{day:02}]");
+  if answers.lines().any(|line| line == header || line.starts_with(&format!("[day-{day:02}."))) {
+    return None;
+  }
+
+  let section = format!("{header}\n\"example.txt\" = {{}}\n\"input.txt\" = {{}}\n");
+  let later = answers.lines().position(|line| {
+    line.strip_prefix("[day-").and_then(|rest| rest.get(..2)).and_then(|number| number.parse::<u8>().ok()).is_some_and(|number| number > day)
+  });
+
+  let mut lines = answers.lines().collect::<Vec<_>>();
+  match later {
+    Some(at) => {
+      lines.insert(at, &section);
+      Some(lines.join("\n") + "\n")
+    }
+    None => Some(format!("{}\n\n{section}", answers.trim_end())),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc-test-scaffold-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("aoc").join("src")).unwrap();
+    fs::create_dir_all(root.join("aoc-wasm").join("src")).unwrap();
+    fs::write(root.join("Cargo.toml"), "[workspace]\nresolver = \"2\"\nmembers = [\n  \"aoc\",\n  \"day-01\",\n  \"day-13\",\n]\n").unwrap();
+    fs::write(
+      root.join("aoc").join("Cargo.toml"),
+      "[package]\nname = \"aoc\"\n\n[dependencies]\nclap = \"4\"\nday-01 = { path = \"../day-01\" }\nserde = \"1\"\n",
+    ).unwrap();
+    fs::write(
+      root.join("aoc").join("src").join("main.rs"),
+      "const DAYS: &[Day] = &[\n  Day::new::<day_01::Day01>().with_generator(day_01::generator::generate),\n];\n\nfn main() {}\n",
+    ).unwrap();
+    fs::write(
+      root.join("aoc-wasm").join("Cargo.toml"),
+      "[package]\nname = \"aoc-wasm\"\n\n[dependencies]\nday-01 = { path = \"../day-01\" }\nwasm-bindgen = \"0.2\"\n",
+    ).unwrap();
+    fs::write(root.join("aoc-wasm").join("src").join("lib.rs"), "const DAYS: &[Day] = &[\n  Day::new::<day_01::Day01>(),\n];\n").unwrap();
+    fs::write(root.join("answers.toml"), "[day-01]\n\"input.txt\" = { part1 = 1 }\n\n[day-13]\n\"input.txt\" = { part1 = 13 }\n").unwrap();
+
+    root
+  }
+
+  #[test]
+  fn scaffold_creates_and_registers_day() {
+    let root = workspace("new");
+    let touched = scaffold(&root, 12).unwrap();
+    assert_eq!(touched.len(), 10);
+
+    let lib = fs::read_to_string(root.join("day-12").join("src").join("lib.rs")).unwrap();
+    assert!(lib.contains("pub struct Day12;") && lib.contains("const DAY: u8 = 12;"));
+    assert!(!lib.contains("{{"));
+    assert!(lib.contains("for line in parsing::lines(reader)"));
+    let main = fs::read_to_string(root.join("day-12").join("src").join("main.rs")).unwrap();
+    assert!(main.contains("aoc_runner::main::<day_12::Day12>();"));
+    assert!(root.join("day-12").join("example.txt").exists());
+
+    assert_eq!(
+      fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+      "[workspace]\nresolver = \"2\"\nmembers = [\n  \"aoc\",\n  \"day-01\",\n  \"day-12\",\n  \"day-13\",\n]\n"
+    );
+    assert_eq!(
+      fs::read_to_string(root.join("aoc").join("Cargo.toml")).unwrap(),
+      "[package]\nname = \"aoc\"\n\n[dependencies]\nclap = \"4\"\nday-01 = { path = \"../day-01\" }\nday-12 = { path = \"../day-12\" }\nserde = \"1\"\n"
+    );
+    assert_eq!(
+      fs::read_to_string(root.join("aoc").join("src").join("main.rs")).unwrap(),
+      "const DAYS: &[Day] = &[\n  Day::new::<day_01::Day01>().with_generator(day_01::generator::generate),\n  Day::new::<day_12::Day12>(),\n];\n\nfn main() {}\n"
+    );
+    assert_eq!(
+      fs::read_to_string(root.join("aoc-wasm").join("Cargo.toml")).unwrap(),
+      "[package]\nname = \"aoc-wasm\"\n\n[dependencies]\nday-01 = { path = \"../day-01\" }\nday-12 = { path = \"../day-12\" }\nwasm-bindgen = \"0.2\"\n"
+    );
+    assert_eq!(
+      fs::read_to_string(root.join("aoc-wasm").join("src").join("lib.rs")).unwrap(),
+      "const DAYS: &[Day] = &[\n  Day::new::<day_01::Day01>(),\n  Day::new::<day_12::Day12>(),\n];\n"
+    );
+    assert_eq!(
+      fs::read_to_string(root.join("answers.toml")).unwrap(),
+      "[day-01]\n\"input.txt\" = { part1 = 1 }\n\n[day-12]\n\"example.txt\" = {}\n\"input.txt\" = {}\n\n[day-13]\n\"input.txt\" = { part1 = 13 }\n"
+    );
+
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn scaffold_refuses_existing_day() {
+    let root = workspace("existing");
+    fs::create_dir(root.join("day-01")).unwrap();
+    let before = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+
+    assert!(matches!(scaffold(&root, 1), Err(ScaffoldError::Exists(_))));
+    assert_eq!(fs::read_to_string(root.join("Cargo.toml")).unwrap(), before);
+
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn scaffold_leaves_tree_alone_when_malformed() {
+    let root = workspace("malformed");
+    fs::write(root.join("aoc").join("src").join("main.rs"), "fn main() {}\n").unwrap();
+
+    let error = scaffold(&root, 14).unwrap_err();
+    assert!(error.to_string().ends_with("main.rs: no DAYS table"), "{error}");
+    assert!(!root.join("day-14").exists());
+    assert!(!fs::read_to_string(root.join("Cargo.toml")).unwrap().contains("day-14"));
+
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn section_after_last_day() {
+    let answers = add_section("[day-01]\n\"input.txt\" = { part1 = 1 }\n", 2).unwrap();
+    assert_eq!(answers, "[day-01]\n\"input.txt\" = { part1 = 1 }\n\n[day-02]\n\"example.txt\" = {}\n\"input.txt\" = {}\n");
+    assert_eq!(add_section("[day-10.\"input.txt\"]\npart1 = 1\n", 10), None);
+  }
+}
@@ -0,0 +1,172 @@
+//! A tiny HTTP server for the WebAssembly playground: the page in
+//! `aoc-wasm/www` and the solvers `wasm-pack` builds into `aoc-wasm/pkg`.
+use std::{
+  fs,
+  io::{self, BufRead, BufReader, Write},
+  net::{TcpListener, TcpStream},
+  path::{Path, PathBuf},
+  thread,
+};
+
+use aoc_runner::workspace_root;
+
+pub const BUILD: &str = "wasm-pack build --target web aoc-wasm";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+  pub status: u16,
+  pub content_type: &'static str,
+  pub body: Vec<u8>,
+}
+
+impl Response {
+  fn text(status: u16, body: &str) -> Self {
+    Response { status, content_type: "text/plain; charset=utf-8", body: body.as_bytes().to_vec() }
+  }
+}
+
+/// The directory of the playground crate, with `www` and `pkg` inside.
+#[must_use]
+pub fn playground_root() -> PathBuf {
+  workspace_root().join("aoc-wasm")
+}
+
+/// The response to a `method` request for `path`: `/pkg/…` from the built
+/// solvers and anything else from the page's directory, `/` being its
+/// `index.html`.
+pub fn respond(root: &Path, method: &str, path: &str) -> Response {
+  if method != "GET" {
+    return Response::text(405, "only GET is supported");
+  }
+
+  let path = path.split(['?', '#']).next().unwrap_or_default();
+  let segments = path.split('/').filter(|segment| !segment.is_empty()).collect::<Vec<_>>();
+  // nothing outside the two directories
+  if segments.iter().any(|segment| segment.starts_with('.') || segment.contains('\\')) {
+    return Response::text(404, "not found");
+  }
+  let file = match segments[..] {
+    [] => root.join("www").join("index.html"),
+    ["pkg", ref rest @ ..] => rest.iter().fold(root.join("pkg"), |path, segment| path.join(segment)),
+    ref rest => rest.iter().fold(root.join("www"), |path, segment| path.join(segment)),
+  };
+
+  match fs::read(&file) {
+    Ok(body) => Response { status: 200, content_type: content_type(&file), body },
+    Err(_) if segments.first() == Some(&"pkg") => Response::text(404, &format!("not found, build the solvers with `{BUILD}`")),
+    Err(_) => Response::text(404, "not found"),
+  }
+}
+
+fn content_type(file: &Path) -> &'static str {
+  match file.extension().and_then(|extension| extension.to_str()) {
+    Some("html") => "text/html; charset=utf-8",
+    Some("js") => "text/javascript",
+    Some("wasm") => "application/wasm",
+    Some("css") => "text/css",
+    Some("json") => "application/json",
+    Some("ts" | "txt") => "text/plain; charset=utf-8",
+    _ => "application/octet-stream",
+  }
+}
+
+/// Answers every connection to `listener` from `root`, each on its own thread,
+/// until the process is stopped.
+///
+/// # Errors
+///
+/// Returns an error if the listener stops accepting connections.
+pub fn serve(listener: &TcpListener, root: &Path) -> io::Result<()> {
+  for stream in listener.incoming() {
+    let (stream, root) = (stream?, root.to_path_buf());
+    thread::spawn(move || {
+      // the browser hanging up early is its own business
+      let _ = answer(&stream, &root);
+    });
+  }
+
+  Ok(())
+}
+
+fn answer(stream: &TcpStream, root: &Path) -> io::Result<()> {
+  let mut reader = BufReader::new(stream);
+  let mut line = String::new();
+  reader.read_line(&mut line)?;
+  let mut parts = line.split_whitespace();
+  let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+  // the headers say nothing a static file needs
+  let mut header = String::new();
+  while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+    header.clear();
+  }
+
+  let response = respond(root, method, path);
+  let mut stream = stream;
+  write!(
+    stream,
+    "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+    response.status,
+    if response.status == 200 { "OK" } else { "Error" },
+    response.content_type,
+    response.body.len()
+  )?;
+  stream.write_all(&response.body)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Read;
+
+  use super::*;
+
+  fn playground(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc-test-serve-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("www")).unwrap();
+    fs::write(root.join("www").join("index.html"), "<h1>playground</h1>").unwrap();
+    fs::write(root.join("secret.txt"), "session").unwrap();
+    root
+  }
+
+  #[test]
+  fn page_and_solvers() {
+    let root = playground("files");
+    assert_eq!(respond(&root, "GET", "/"), Response { status: 200, content_type: "text/html; charset=utf-8", body: b"<h1>playground</h1>".to_vec() });
+    assert_eq!(respond(&root, "GET", "/index.html?day=5").status, 200);
+
+    let missing = respond(&root, "GET", "/pkg/aoc_wasm.js");
+    assert_eq!((missing.status, String::from_utf8(missing.body).unwrap()), (404, format!("not found, build the solvers with `{BUILD}`")));
+
+    fs::create_dir_all(root.join("pkg")).unwrap();
+    fs::write(root.join("pkg").join("aoc_wasm_bg.wasm"), b"\0asm").unwrap();
+    let solvers = respond(&root, "GET", "/pkg/aoc_wasm_bg.wasm");
+    assert_eq!((solvers.status, solvers.content_type, solvers.body), (200, "application/wasm", b"\0asm".to_vec()));
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn nothing_outside_the_playground() {
+    let root = playground("outside");
+    assert_eq!(respond(&root, "GET", "/../secret.txt").status, 404);
+    assert_eq!(respond(&root, "GET", "/pkg/../../secret.txt").status, 404);
+    assert_eq!(respond(&root, "POST", "/").status, 405);
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn over_http() {
+    let root = playground("http");
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let served = root.clone();
+    thread::spawn(move || serve(&listener, &served));
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(stream, "GET / HTTP/1.1\r\nHost: {address}\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: 19\r\n"), "{response}");
+    assert!(response.ends_with("\r\n\r\n<h1>playground</h1>"), "{response}");
+    fs::remove_dir_all(root).unwrap();
+  }
+}
@@ -0,0 +1,106 @@
+//! A tiny HTTP/1.1 server on a random local port that stands in for
+//! adventofcode.com in tests.
+use std::{
+  io::{BufRead, BufReader, Read, Write},
+  net::{TcpListener, TcpStream},
+  sync::{Arc, Mutex},
+  thread,
+  time::Duration,
+};
+
+use crate::config::Config;
+
+#[derive(Debug, Clone)]
+pub struct Recorded {
+  pub method: String,
+  pub path: String,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+}
+
+impl Recorded {
+  /// The value of header `name`, ignoring case.
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self.headers.iter().find(|(header, _)| header.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+  }
+}
+
+type Handler = dyn Fn(&Recorded) -> (u16, String) + Send + Sync;
+
+pub struct StandIn {
+  pub url: String,
+  requests: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl StandIn {
+  /// Starts serving in the background, answering every request with the
+  /// status and body `handler` picks. The server lives until the test ends.
+  pub fn start(handler: impl Fn(&Recorded) -> (u16, String) + Send + Sync + 'static) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("can bind a local port");
+    let url = format!("http://{}", listener.local_addr().expect("listener has an address"));
+    let requests = Arc::new(Mutex::new(vec![]));
+
+    let recorded = Arc::clone(&requests);
+    let handler: Arc<Handler> = Arc::new(handler);
+    thread::spawn(move || {
+      for stream in listener.incoming().flatten() {
+        let (recorded, handler) = (Arc::clone(&recorded), Arc::clone(&handler));
+        thread::spawn(move || serve(&stream, &recorded, handler.as_ref()));
+      }
+    });
+
+    StandIn { url, requests }
+  }
+
+  /// A config pointing at this server, caching under a fresh temporary
+  /// directory named after `name`.
+  pub fn config(&self, name: &str) -> Config {
+    let cache_dir = std::env::temp_dir().join(format!("aoc-test-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    Config {
+      session: Some(String::from("secret")),
+      user: Some(String::from("tester")),
+      base_url: self.url.clone(),
+      cache_dir,
+      min_interval: Duration::ZERO,
+    }
+  }
+
+  /// Every request served so far.
+  pub fn requests(&self) -> Vec<Recorded> {
+    self.requests.lock().expect("no test thread panicked holding the lock").clone()
+  }
+}
+
+fn serve(stream: &TcpStream, recorded: &Mutex<Vec<Recorded>>, handler: &Handler) {
+  let mut reader = BufReader::new(stream);
+  let mut line = String::new();
+  if reader.read_line(&mut line).is_err() {
+    return;
+  }
+  let mut parts = line.split_whitespace();
+  let (method, path) = (parts.next().unwrap_or_default().to_string(), parts.next().unwrap_or_default().to_string());
+
+  let mut headers = vec![];
+  loop {
+    let mut line = String::new();
+    if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+      break;
+    }
+    if let Some((name, value)) = line.split_once(':') {
+      headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+  }
+
+  let length = headers.iter().find(|(name, _)| name.eq_ignore_ascii_case("content-length")).and_then(|(_, value)| value.parse().ok()).unwrap_or(0);
+  let mut body = vec![0; length];
+  if reader.read_exact(&mut body).is_err() {
+    return;
+  }
+
+  let request = Recorded { method, path, headers, body: String::from_utf8_lossy(&body).into_owned() };
+  let (status, body) = handler(&request);
+  recorded.lock().expect("no test thread panicked holding the lock").push(request);
+
+  let _ = write!(&*stream, "HTTP/1.1 {status} Stand-In\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+}
@@ -0,0 +1,312 @@
+use std::{
+  fmt::{self, Display},
+  fs,
+  io,
+  path::{Path, PathBuf},
+  time::Duration,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  client::{self, Client, ClientError, Method, Transport},
+  config::YEAR,
+};
+
+/// What adventofcode.com made of an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+  Right,
+  Wrong {
+    hint: Option<Hint>,
+    /// How long the site asks to wait before the next guess.
+    wait: Option<Duration>,
+  },
+  /// Another answer was sent too recently, nothing was checked.
+  TooSoon(Option<Duration>),
+  /// The part is already solved, nothing was checked.
+  AlreadySolved,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+  TooHigh,
+  TooLow,
+}
+
+impl Display for Verdict {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Verdict::Right => write!(f, "right answer"),
+      Verdict::Wrong { hint: Some(Hint::TooHigh), .. } => write!(f, "wrong answer, too high"),
+      Verdict::Wrong { hint: Some(Hint::TooLow), .. } => write!(f, "wrong answer, too low"),
+      Verdict::Wrong { hint: None, .. } => write!(f, "wrong answer"),
+      Verdict::TooSoon(Some(wait)) => write!(f, "answered too recently, {}s left to wait", wait.as_secs()),
+      Verdict::TooSoon(None) => write!(f, "answered too recently"),
+      Verdict::AlreadySolved => write!(f, "already solved"),
+    }
+  }
+}
+
+/// Reads the verdict out of the page the site answers a submission with.
+#[must_use]
+pub fn parse_verdict(page: &str) -> Option<Verdict> {
+  if page.contains("That's the right answer") {
+    Some(Verdict::Right)
+  } else if page.contains("That's not the right answer") {
+    let hint = if page.contains("your answer is too high") {
+      Some(Hint::TooHigh)
+    } else if page.contains("your answer is too low") {
+      Some(Hint::TooLow)
+    } else {
+      None
+    };
+    // "Please wait one minute before trying again.", or "5 minutes" later on,
+    // in whatever case the sentence comes
+    let wait = page.to_lowercase();
+    let wait = wait.split_once("please wait ").and_then(|(_, rest)| rest.split_once(" before")).and_then(|(wait, _)| match wait.split_once(' ') {
+      Some(("one", "minute")) => Some(Duration::from_mins(1)),
+      Some((minutes, "minutes")) => minutes.parse().ok().map(Duration::from_mins),
+      _ => None,
+    });
+    Some(Verdict::Wrong { hint, wait })
+  } else if page.contains("You gave an answer too recently") {
+    // "You have 1m 4s left to wait." or "You have 33s left to wait."
+    let wait = page.split_once("You have ").and_then(|(_, rest)| rest.split_once(" left to wait")).and_then(|(wait, _)| {
+      wait.split(' ').try_fold(0, |seconds, part| match part.split_at(part.len().saturating_sub(1)) {
+        (minutes, "m") => minutes.parse::<u64>().ok().map(|minutes| seconds + minutes * 60),
+        (secs, "s") => secs.parse::<u64>().ok().map(|secs| seconds + secs),
+        _ => None,
+      })
+    });
+    Some(Verdict::TooSoon(wait.map(Duration::from_secs)))
+  } else if page.contains("You don't seem to be solving the right level") {
+    Some(Verdict::AlreadySolved)
+  } else {
+    None
+  }
+}
+
+/// One answer the site has ruled on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+  pub day: u8,
+  pub part: u8,
+  pub answer: String,
+  /// `right`, `wrong`, `too high` or `too low`.
+  pub verdict: String,
+  /// Seconds since the Unix epoch.
+  pub at: u64,
+}
+
+/// Every answer submitted from this machine for one user, kept next to their
+/// cached inputs as `submissions.toml`, so known-wrong answers are never sent
+/// twice and the site's waits are honoured without asking it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+  /// No answer may be sent before this many seconds since the Unix epoch.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub retry_after: Option<u64>,
+  #[serde(default, rename = "submission")]
+  pub entries: Vec<Entry>,
+}
+
+impl History {
+  /// # Errors
+  ///
+  /// Returns an error if the history exists but cannot be read or parsed.
+  pub fn load(path: &Path) -> Result<Self, ClientError> {
+    match fs::read_to_string(path) {
+      Ok(text) => toml::from_str(&text).map_err(|error| ClientError::History { path: path.to_path_buf(), message: error.message().to_string() }),
+      Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+      Err(source) => Err(ClientError::Io { path: path.to_path_buf(), source }),
+    }
+  }
+
+  /// # Errors
+  ///
+  /// Returns an error if the history cannot be written.
+  pub fn save(&self, path: &Path) -> Result<(), ClientError> {
+    let text = toml::to_string(self).map_err(|error| ClientError::History { path: path.to_path_buf(), message: error.to_string() })?;
+    client::write(path, &text)
+  }
+
+  /// Why `answer` should not be sent at `now`, if there is a reason.
+  #[must_use]
+  pub fn refusal(&self, day: u8, part: u8, answer: &str, now: u64) -> Option<String> {
+    if let Some(wait) = self.retry_after.and_then(|retry_after| retry_after.checked_sub(now)).filter(|wait| *wait > 0) {
+      return Some(format!("the site asked to wait another {wait}s"));
+    }
+
+    let entries = self.entries.iter().filter(|entry| entry.day == day && entry.part == part);
+    let number = answer.parse::<i64>().ok();
+    for entry in entries {
+      let bound = entry.answer.parse::<i64>().ok();
+      match (entry.verdict.as_str(), number, bound) {
+        ("right", ..) if entry.answer == answer => return Some(format!("`{answer}` was already accepted")),
+        ("right", ..) => return Some(format!("part {part} was already solved with `{}`", entry.answer)),
+        (verdict, ..) if entry.answer == answer => return Some(format!("`{answer}` was already rejected ({verdict})")),
+        ("too high", Some(number), Some(bound)) if number >= bound => return Some(format!("`{answer}` is not below `{bound}`, which was too high")),
+        ("too low", Some(number), Some(bound)) if number <= bound => return Some(format!("`{answer}` is not above `{bound}`, which was too low")),
+        _ => {}
+      }
+    }
+
+    None
+  }
+
+  /// Remembers what the site said about `answer`.
+  pub fn record(&mut self, day: u8, part: u8, answer: &str, verdict: Verdict, now: u64) {
+    let (verdict, wait) = match verdict {
+      Verdict::Right => ("right", None),
+      Verdict::Wrong { hint: Some(Hint::TooHigh), wait } => ("too high", wait),
+      Verdict::Wrong { hint: Some(Hint::TooLow), wait } => ("too low", wait),
+      Verdict::Wrong { hint: None, wait } => ("wrong", wait),
+      Verdict::TooSoon(wait) => {
+        self.retry_after = wait.map(|wait| now + wait.as_secs());
+        return;
+      }
+      Verdict::AlreadySolved => return,
+    };
+
+    self.retry_after = wait.map(|wait| now + wait.as_secs());
+    self.entries.push(Entry { day, part, answer: answer.to_string(), verdict: verdict.to_string(), at: now });
+  }
+}
+
+/// The submission history of the client's user.
+///
+/// # Errors
+///
+/// Returns an error if there is neither a user name nor a session token.
+pub fn history_path<T: Transport>(client: &Client<T>) -> Result<PathBuf, ClientError> {
+  Ok(client.config().user_cache_dir()?.join("submissions.toml"))
+}
+
+/// Sends `answer` for `part` of `day` unless the history already rules it out,
+/// and records the verdict.
+///
+/// # Errors
+///
+/// Returns an error if the answer is refused locally, the request fails, the
+/// response cannot be understood, or the history cannot be read or written.
+pub fn submit<T: Transport>(client: &Client<T>, day: u8, part: u8, answer: &str) -> Result<Verdict, ClientError> {
+  let path = history_path(client)?;
+  let mut history = History::load(&path)?;
+  let now = client::now().as_secs();
+  if let Some(reason) = history.refusal(day, part, answer, now) {
+    return Err(ClientError::Refused(reason));
+  }
+
+  let url = format!("/{YEAR}/day/{day}/answer");
+  let response = client.send(Method::Post, &url, Some(format!("level={part}&answer={}", form_encode(answer))))?;
+  let verdict = match response.status {
+    200 => parse_verdict(&response.body).ok_or(ClientError::UnexpectedResponse(url))?,
+    302 | 400 | 401 => return Err(ClientError::SessionRejected),
+    404 => return Err(ClientError::NotUnlocked(day)),
+    status => return Err(ClientError::Status { status, url }),
+  };
+
+  history.record(day, part, answer, verdict, now);
+  history.save(&path)?;
+
+  Ok(verdict)
+}
+
+fn form_encode(text: &str) -> String {
+  text.bytes().map(|byte| match byte {
+    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => char::from(byte).to_string(),
+    b' ' => String::from("+"),
+    byte => format!("%{byte:02X}"),
+  }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{client::HttpTransport, stand_in::StandIn};
+
+  const RIGHT: &str = "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer to saving your vacation.</p></article></main>";
+  const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2022/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. [<a href=\"/2022/day/1\">Return to Day 1</a>]</p></article></main>";
+  const TOO_SOON: &str = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 4s left to wait. [<a href=\"/2022/day/1\">Return to Day 1</a>]</p></article></main>";
+  const SOLVED: &str = "<main><article><p>You don't seem to be solving the right level.  Did you already complete it? [<a href=\"/2022/day/1\">Return to Day 1</a>]</p></article></main>";
+
+  #[test]
+  fn parse_site_responses() {
+    assert_eq!(parse_verdict(RIGHT), Some(Verdict::Right));
+    assert_eq!(parse_verdict(TOO_HIGH), Some(Verdict::Wrong { hint: Some(Hint::TooHigh), wait: Some(Duration::from_mins(1)) }));
+    let later = TOO_HIGH.replace("Please wait one minute", "please wait 5 minutes");
+    assert_eq!(parse_verdict(&later), Some(Verdict::Wrong { hint: Some(Hint::TooHigh), wait: Some(Duration::from_mins(5)) }));
+    assert_eq!(parse_verdict(TOO_SOON), Some(Verdict::TooSoon(Some(Duration::from_secs(64)))));
+    assert_eq!(parse_verdict(SOLVED), Some(Verdict::AlreadySolved));
+    assert_eq!(parse_verdict("<html>Advent of Code</html>"), None);
+  }
+
+  #[test]
+  fn history_refuses_known_answers() {
+    let mut history = History::default();
+    history.record(1, 1, "500", Verdict::Wrong { hint: Some(Hint::TooHigh), wait: None }, 100);
+    history.record(1, 1, "100", Verdict::Wrong { hint: Some(Hint::TooLow), wait: None }, 200);
+
+    assert!(history.refusal(1, 1, "500", 300).unwrap().contains("already rejected"));
+    assert!(history.refusal(1, 1, "600", 300).unwrap().contains("too high"));
+    assert!(history.refusal(1, 1, "50", 300).unwrap().contains("too low"));
+    assert_eq!(history.refusal(1, 1, "250", 300), None);
+    assert_eq!(history.refusal(1, 2, "500", 300), None);
+
+    history.record(1, 1, "250", Verdict::Right, 400);
+    assert!(history.refusal(1, 1, "251", 500).unwrap().contains("already solved"));
+  }
+
+  #[test]
+  fn history_honours_waits() {
+    let mut history = History::default();
+    history.record(2, 1, "7", Verdict::TooSoon(Some(Duration::from_secs(30))), 1000);
+    assert!(history.refusal(2, 1, "8", 1010).unwrap().contains("another 20s"));
+    assert_eq!(history.refusal(2, 1, "8", 1030), None);
+  }
+
+  #[test]
+  fn history_round_trips() {
+    let path = std::env::temp_dir().join(format!("aoc-test-history-{}.toml", std::process::id()));
+    let mut history = History::default();
+    history.record(3, 2, "CMZ", Verdict::Wrong { hint: None, wait: Some(Duration::from_mins(1)) }, 10);
+    history.save(&path).unwrap();
+    assert_eq!(History::load(&path).unwrap(), history);
+    let _ = fs::remove_file(path);
+  }
+
+  #[test]
+  fn submit_posts_answer_and_never_repeats_a_wrong_one() {
+    let server = StandIn::start(|request| match request.body.as_str() {
+      "level=2&answer=45000" => (200, String::from(RIGHT)),
+      _ => (200, String::from(TOO_HIGH).replace("Please wait one minute before trying again.", "")),
+    });
+    let client = Client::new(HttpTransport::new(), server.config("submit"));
+
+    let verdict = submit(&client, 1, 2, "99999").unwrap();
+    assert_eq!(verdict, Verdict::Wrong { hint: Some(Hint::TooHigh), wait: None });
+    assert!(matches!(submit(&client, 1, 2, "99999"), Err(ClientError::Refused(_))));
+    assert!(matches!(submit(&client, 1, 2, "100000"), Err(ClientError::Refused(_))));
+    assert_eq!(submit(&client, 1, 2, "45000").unwrap(), Verdict::Right);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!((requests[1].method.as_str(), requests[1].path.as_str()), ("POST", "/2022/day/1/answer"));
+    assert_eq!(requests[1].header("content-type"), Some("application/x-www-form-urlencoded"));
+  }
+
+  #[test]
+  fn submit_remembers_waits() {
+    let server = StandIn::start(|_| (200, String::from(TOO_SOON)));
+    let client = Client::new(HttpTransport::new(), server.config("too-soon"));
+
+    assert_eq!(submit(&client, 1, 1, "1").unwrap(), Verdict::TooSoon(Some(Duration::from_secs(64))));
+    assert!(matches!(submit(&client, 1, 1, "2"), Err(ClientError::Refused(_))));
+    assert_eq!(server.requests().len(), 1);
+  }
+
+  #[test]
+  fn form_encoding() {
+    assert_eq!(form_encode("SVF DL&x"), "SVF+DL%26x");
+  }
+}
@@ -0,0 +1,440 @@
+mod visual;
+
+use std::{
+  fs,
+  io,
+  path::PathBuf,
+};
+
+use aoc_runner::{
+  registry::{Check, Outcome, Registry},
+  Answers, Day, DayError,
+};
+use ratatui::{
+  crossterm::event::{self, Event, KeyCode, KeyEventKind},
+  layout::{Constraint, Layout, Rect},
+  style::{Color, Modifier, Style},
+  text::{Line, Span},
+  widgets::{Block, Clear, List, ListItem, ListState, Paragraph},
+  Frame,
+};
+
+use self::visual::Visual;
+
+// status, day, both answers and the time, see `day_row`
+const LIST_WIDTH: u16 = 49;
+const ANSWER_WIDTH: usize = 12;
+
+/// How a day fares against its registered inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+  /// Every registered input on disk gets the expected answers.
+  Verified,
+  /// At least one registered input gets a wrong answer or an error.
+  Failing,
+  /// No registered input on disk to check it against.
+  Unsolved,
+}
+
+impl Status {
+  fn of(checks: &[Check]) -> Self {
+    if checks.iter().any(|check| check.outcome.is_failure()) {
+      Status::Failing
+    } else if checks.iter().any(|check| matches!(check.outcome, Outcome::Pass)) {
+      Status::Verified
+    } else {
+      Status::Unsolved
+    }
+  }
+
+  fn symbol(self) -> Span<'static> {
+    match self {
+      Status::Verified => Span::styled("✓", Style::new().fg(Color::Green)),
+      Status::Failing => Span::styled("✗", Style::new().fg(Color::Red)),
+      Status::Unsolved => Span::styled("·", Style::new().fg(Color::DarkGray)),
+    }
+  }
+}
+
+struct DayView {
+  day: &'static Day,
+  input: PathBuf,
+  checks: Vec<Check>,
+  answers: Result<Answers, DayError>,
+  // worked out the first time the pane is shown for this input
+  visual: Option<Result<Visual, DayError>>,
+}
+
+impl DayView {
+  fn status(&self) -> Status {
+    Status::of(&self.checks)
+  }
+
+  // the input by its path inside the day's directory when it is there
+  fn input_name(&self) -> String {
+    let directory = self.day.directory();
+    self.input.strip_prefix(&directory).unwrap_or(&self.input).display().to_string()
+  }
+}
+
+struct Picker {
+  inputs: Vec<PathBuf>,
+  selected: usize,
+}
+
+/// The state of `aoc tui`, driven one key at a time.
+pub struct App {
+  days: Vec<DayView>,
+  selected: usize,
+  registry: Result<Registry, String>,
+  picker: Option<Picker>,
+  show_visual: bool,
+  done: bool,
+}
+
+impl App {
+  /// Checks every day against its registered inputs and solves its `input.txt`.
+  #[must_use]
+  pub fn new(days: &'static [Day]) -> Self {
+    let registry = Registry::load().map_err(|error| error.to_string());
+    let days = days.iter().map(|day| {
+      let input = day.directory().join("input.txt");
+      let checks = registry.as_ref().map(|registry| registry.check(day)).unwrap_or_default();
+      let answers = day.run(&input);
+      DayView { day, input, checks, answers, visual: None }
+    });
+
+    App { days: days.collect(), selected: 0, registry, picker: None, show_visual: false, done: false }
+  }
+
+  /// Whether the user asked to quit.
+  #[must_use]
+  pub fn is_done(&self) -> bool {
+    self.done
+  }
+
+  pub fn key(&mut self, code: KeyCode) {
+    if let Some(picker) = self.picker.take() {
+      self.pick(picker, code);
+    } else {
+      match code {
+        KeyCode::Char('q') | KeyCode::Esc => self.done = true,
+        KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(self.days.len() - 1),
+        KeyCode::Char('r') => self.rerun(),
+        KeyCode::Char('i') => self.picker = Some(Picker { inputs: self.inputs(), selected: 0 }),
+        KeyCode::Char('v') => self.show_visual = !self.show_visual,
+        _ => {}
+      }
+    }
+
+    if self.show_visual {
+      let view = &mut self.days[self.selected];
+      if view.visual.is_none() {
+        view.visual = visual::visualize(view.day.number, &view.input);
+      }
+    }
+  }
+
+  fn pick(&mut self, mut picker: Picker, code: KeyCode) {
+    match code {
+      KeyCode::Up | KeyCode::Char('k') => picker.selected = picker.selected.saturating_sub(1),
+      KeyCode::Down | KeyCode::Char('j') => picker.selected = (picker.selected + 1).min(picker.inputs.len().saturating_sub(1)),
+      KeyCode::Enter => {
+        if let Some(input) = picker.inputs.get(picker.selected) {
+          self.days[self.selected].input.clone_from(input);
+          self.rerun();
+        }
+        return;
+      }
+      KeyCode::Esc | KeyCode::Char('q') => return,
+      _ => {}
+    }
+
+    self.picker = Some(picker);
+  }
+
+  // the text files in the day's directory and every registered input on disk
+  fn inputs(&self) -> Vec<PathBuf> {
+    let view = &self.days[self.selected];
+    let directory = view.day.directory();
+    let mut inputs = fs::read_dir(&directory).into_iter().flatten().filter_map(|entry| {
+      let path = entry.ok()?.path();
+      (path.extension()? == "txt").then_some(path)
+    }).collect::<Vec<_>>();
+    if let Ok(registry) = &self.registry {
+      inputs.extend(registry.inputs(view.day.number).map(|(name, _)| directory.join(name)).filter(|path| path.exists()));
+    }
+    inputs.sort();
+    inputs.dedup();
+
+    inputs
+  }
+
+  // reloads the registry, so answers added while the dashboard is open count
+  fn rerun(&mut self) {
+    self.registry = Registry::load().map_err(|error| error.to_string());
+    let view = &mut self.days[self.selected];
+    view.checks = self.registry.as_ref().map(|registry| registry.check(view.day)).unwrap_or_default();
+    view.answers = view.day.run(&view.input);
+    view.visual = None;
+  }
+
+  pub fn draw(&self, frame: &mut Frame) {
+    let [main, help] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [list, right] = Layout::horizontal([Constraint::Length(LIST_WIDTH), Constraint::Min(0)]).areas(main);
+    let view = &self.days[self.selected];
+
+    let rows = self.days.iter().map(|view| ListItem::new(day_row(view))).collect::<Vec<_>>();
+    let days = List::new(rows)
+      .block(Block::bordered().title(" days "))
+      .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(days, list, &mut ListState::default().with_selected(Some(self.selected)));
+
+    let details = self.details(view);
+    let title = visual::title(view.day.number).filter(|_| self.show_visual);
+    let [details_area, visual_area] = match title {
+      // the details as they are, and the picture below them
+      Some(_) => Layout::vertical([Constraint::Max(u16::try_from(details.len() + 2).unwrap_or(u16::MAX)), Constraint::Min(0)]).areas(right),
+      None => [right, Rect::default()],
+    };
+    frame.render_widget(Paragraph::new(details).block(Block::bordered().title(format!(" day {:02} ", view.day.number))), details_area);
+    if let Some(title) = title {
+      let block = Block::bordered().title(format!(" {title} "));
+      let inner = block.inner(visual_area);
+      frame.render_widget(block, visual_area);
+      frame.render_widget(Paragraph::new(picture(view.visual.as_ref(), inner)), inner);
+    }
+
+    let keys = if self.picker.is_some() {
+      "↑/↓ select  enter solve  esc cancel"
+    } else {
+      "↑/↓ select  r re-run  i input  v visual  q quit"
+    };
+    frame.render_widget(Paragraph::new(keys).style(Style::new().fg(Color::DarkGray)), help);
+
+    if let Some(picker) = &self.picker {
+      self.draw_picker(frame, picker, main);
+    }
+  }
+
+  fn details(&self, view: &DayView) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(format!("input     {}", view.input_name()))];
+    match &view.answers {
+      Ok(answers) => {
+        for (part, answer) in [("part one", &answers.part_one), ("part two", &answers.part_two)] {
+          if answer.contains('\n') {
+            lines.push(Line::from(part));
+            lines.extend(answer.lines().map(|row| Line::from(format!("  {row}"))));
+          } else {
+            lines.push(Line::from(format!("{part}  {answer}")));
+          }
+        }
+        let timings = answers.timings;
+        lines.push(Line::from(format!(
+          "time      {:.2?} (parse {:.2?}, part one {:.2?}, part two {:.2?})",
+          timings.total(),
+          timings.parse,
+          timings.part_one,
+          timings.part_two
+        )));
+      }
+      Err(error) => lines.push(Line::styled(format!("error     {error}"), Style::new().fg(Color::Red))),
+    }
+
+    lines.push(Line::from(""));
+    match &self.registry {
+      Ok(_) if view.checks.is_empty() => lines.push(Line::styled("no inputs in answers.toml", Style::new().fg(Color::DarkGray))),
+      Ok(_) => {
+        for check in &view.checks {
+          let color = match check.outcome {
+            Outcome::Pass => Color::Green,
+            Outcome::Missing => Color::DarkGray,
+            Outcome::Mismatch(_) | Outcome::Failed(_) => Color::Red,
+          };
+          let detail = check.outcome.to_string().replace('\n', " ");
+          lines.push(Line::from(vec![
+            Span::styled(format!("{:8}  ", check.outcome.name()), Style::new().fg(color)),
+            Span::raw(format!("{}  {detail}", check.input).trim_end().to_string()),
+          ]));
+        }
+      }
+      Err(error) => lines.push(Line::styled(format!("answers.toml: {error}"), Style::new().fg(Color::Red))),
+    }
+
+    lines
+  }
+
+  fn draw_picker(&self, frame: &mut Frame, picker: &Picker, area: Rect) {
+    let directory = self.days[self.selected].day.directory();
+    let names = picker.inputs.iter().map(|input| ListItem::new(input.strip_prefix(&directory).unwrap_or(input).display().to_string()));
+    let height = u16::try_from(picker.inputs.len() + 2).unwrap_or(u16::MAX).clamp(3, area.height);
+    let width = (area.width / 2).max(30).min(area.width);
+    let popup = Rect { x: area.x + (area.width - width) / 2, y: area.y + (area.height - height) / 2, width, height };
+
+    let inputs = List::new(names)
+      .block(Block::bordered().title(" input "))
+      .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    frame.render_widget(Clear, popup);
+    frame.render_stateful_widget(inputs, popup, &mut ListState::default().with_selected(Some(picker.selected)));
+  }
+}
+
+fn day_row(view: &DayView) -> Line<'static> {
+  let (part_one, part_two, time) = match &view.answers {
+    Ok(answers) => (cell(&answers.part_one), cell(&answers.part_two), format!("{:.2?}", answers.timings.total())),
+    Err(_) => (String::from("error"), String::new(), String::new()),
+  };
+
+  Line::from(vec![
+    view.status().symbol(),
+    Span::raw(format!(" {:02}  {part_one:ANSWER_WIDTH$}  {part_two:ANSWER_WIDTH$}  {time:>9}", view.day.number)),
+  ])
+}
+
+// an answer cut to fit its column, drawings and all
+fn cell(answer: &str) -> String {
+  if answer.contains('\n') {
+    String::from("(drawing)")
+  } else if answer.chars().count() > ANSWER_WIDTH {
+    answer.chars().take(ANSWER_WIDTH - 1).chain(['…']).collect()
+  } else {
+    answer.to_string()
+  }
+}
+
+// the picture shrunk to fit `area`, one character per cell
+fn picture(visual: Option<&Result<Visual, DayError>>, area: Rect) -> Vec<Line<'static>> {
+  let (width, height) = (usize::from(area.width), usize::from(area.height));
+  match visual {
+    None => vec![],
+    Some(Err(error)) => vec![Line::styled(error.to_string(), Style::new().fg(Color::Red))],
+    Some(Ok(Visual::Text(lines))) => lines.iter().map(|line| Line::from(line.clone())).collect(),
+    Some(Ok(Visual::Heatmap(grid))) => {
+      let grid = visual::shrink(grid, width, height);
+      let brightest = grid.iter().map(|(_, &value)| value).max().unwrap_or_default().isqrt();
+      let shade = |value: &u32| {
+        // 24 shades of grey from 232 to 255, on a square root so a few huge scores do not wash out the rest
+        let level = (value.isqrt() * 23).checked_div(brightest).unwrap_or_default();
+        Span::styled("█", Style::new().fg(Color::Indexed(232 + u8::try_from(level).unwrap_or(23))))
+      };
+      grid.rows().map(|row| Line::from(row.iter().map(shade).collect::<Vec<_>>())).collect()
+    }
+    Some(Ok(Visual::Trail(trail))) => {
+      let grid = visual::shrink(&day_09::trail_grid(trail), width, height);
+      let cell = |value: &u32| match value {
+        0 => Span::raw(" "),
+        1 => Span::raw("#"),
+        _ => Span::styled("s", Style::new().fg(Color::Yellow)),
+      };
+      grid.rows().map(|row| Line::from(row.iter().map(cell).collect::<Vec<_>>())).collect()
+    }
+  }
+}
+
+/// Shows the dashboard until the user quits, restoring the terminal either way.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be set up, drawn to or read from.
+pub fn run(days: &'static [Day]) -> io::Result<()> {
+  let mut app = App::new(days);
+  let mut terminal = ratatui::try_init()?;
+  let result = (|| {
+    while !app.is_done() {
+      terminal.draw(|frame| app.draw(frame))?;
+      if let Event::Key(key) = event::read()? {
+        if key.kind == KeyEventKind::Press {
+          app.key(key.code);
+        }
+      }
+    }
+    Ok(())
+  })();
+  ratatui::restore();
+
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use ratatui::{backend::TestBackend, Terminal};
+
+  use super::*;
+
+  const DAYS: &[Day] = &[Day::new::<day_05::Day05>(), Day::new::<day_06::Day06>(), Day::new::<day_10::Day10>()];
+
+  fn screen(app: &App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let rows = buffer.content.chunks(usize::from(buffer.area.width)).map(|row| row.iter().map(ratatui::buffer::Cell::symbol).collect::<String>());
+    rows.collect::<Vec<_>>().join("\n")
+  }
+
+  #[test]
+  fn status_from_checks() {
+    let check = |outcome| Check { day: 1, input: String::from("input.txt"), outcome };
+    assert_eq!(Status::of(&[check(Outcome::Pass), check(Outcome::Missing)]), Status::Verified);
+    assert_eq!(Status::of(&[check(Outcome::Pass), check(Outcome::Failed(DayError::unsolvable("no")))]), Status::Failing);
+    assert_eq!(Status::of(&[check(Outcome::Missing)]), Status::Unsolved);
+    assert_eq!(Status::of(&[]), Status::Unsolved);
+  }
+
+  #[test]
+  fn answers_cut_to_their_column() {
+    assert_eq!(cell("CMZ"), "CMZ");
+    assert_eq!(cell("1234567890123"), "12345678901…");
+    assert_eq!(cell("█ █\n ██"), "(drawing)");
+  }
+
+  #[test]
+  fn list_and_details() {
+    let mut app = App::new(DAYS);
+    assert!(app.days.iter().all(|view| view.status() == Status::Verified));
+
+    app.key(KeyCode::Down);
+    let screen = screen(&app);
+    assert!(screen.contains("✓ 06"), "{screen}");
+    assert!(screen.contains(" day 06 "), "{screen}");
+    assert!(screen.contains("pass      example_1.txt"), "{screen}");
+  }
+
+  #[test]
+  fn pick_another_input() {
+    let mut app = App::new(DAYS);
+    app.key(KeyCode::Char('i'));
+    let picker = app.picker.as_ref().unwrap();
+    let example = picker.inputs.iter().position(|input| input.ends_with("example.txt")).unwrap();
+    assert!(screen(&app).contains("malformed.txt"));
+
+    for _ in 0..example {
+      app.key(KeyCode::Down);
+    }
+    app.key(KeyCode::Enter);
+    assert!(app.picker.is_none());
+    assert_eq!(app.days[0].input_name(), "example.txt");
+    assert_eq!(app.days[0].answers.as_ref().unwrap().part_one, "CMZ");
+
+    app.key(KeyCode::Char('i'));
+    app.key(KeyCode::Esc);
+    assert!(app.picker.is_none() && !app.is_done());
+    app.key(KeyCode::Char('q'));
+    assert!(app.is_done());
+  }
+
+  #[test]
+  fn visual_pane() {
+    let mut app = App::new(DAYS);
+    app.key(KeyCode::Char('v'));
+    assert!(screen(&app).contains(" stacks "));
+
+    app.key(KeyCode::Down);
+    assert!(app.days[1].visual.is_none());
+    assert!(!screen(&app).contains(" stacks "));
+
+    app.key(KeyCode::Down);
+    assert!(matches!(app.days[2].visual, Some(Ok(Visual::Text(_)))));
+    assert!(screen(&app).contains(" CRT "));
+  }
+}
@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use aoc_runner::{DayError, Solution};
+use grid::{Grid, Point};
+
+/// A picture of how a day got to its answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Visual {
+  /// Lines shown as they are, e.g. day 10's CRT.
+  Text(Vec<String>),
+  /// One value per cell, drawn from dark to bright, e.g. day 8's scenic scores.
+  Heatmap(Grid<u32>),
+  /// The whole path of something through the cells, in order and coming back
+  /// to a cell as often as it did, e.g. day 9's rope tail.
+  Trail(Vec<Point>),
+}
+
+/// The title of the pane showing the picture of `day`, or `None` if it has none.
+#[must_use]
+pub fn title(day: u8) -> Option<&'static str> {
+  match day {
+    5 => Some("stacks"),
+    8 => Some("scenic scores"),
+    9 => Some("rope tail"),
+    10 => Some("CRT"),
+    _ => None,
+  }
+}
+
+/// The picture of `day` solving `input`, or `None` if it has none.
+pub fn visualize(day: u8, input: &Path) -> Option<Result<Visual, DayError>> {
+  let visual = match day {
+    5 => stacks(input),
+    8 => scenic_scores(input),
+    9 => rope_tail(input),
+    10 => crt(input),
+    _ => return None,
+  };
+
+  Some(visual)
+}
+
+fn parse<S: Solution>(input: &Path) -> Result<S::Input, DayError> {
+  S::parse(aoc_runner::open(input)?).map_err(|error| error.in_file(input))
+}
+
+fn stacks(input: &Path) -> Result<Visual, DayError> {
+  let plan = parse::<day_05::Day05>(input)?;
+  Ok(Visual::Text(plan.draw_both()?))
+}
+
+fn scenic_scores(input: &Path) -> Result<Visual, DayError> {
+  let forest = parse::<day_08::Day08>(input)?;
+  Ok(Visual::Heatmap(forest.trees.map(|tree, _| forest.calculate_score_for_position(tree))))
+}
+
+fn rope_tail(input: &Path) -> Result<Visual, DayError> {
+  let motions = parse::<day_09::Day09>(input)?;
+  Ok(Visual::Trail(day_09::tail_trail(&motions, 9)))
+}
+
+fn crt(input: &Path) -> Result<Visual, DayError> {
+  let program = parse::<day_10::Day10>(input)?;
+  let screen = day_10::Day10::part_two(&program)?;
+
+  Ok(Visual::Text(screen.lines().map(String::from).collect()))
+}
+
+/// `grid` shrunk to at most `width` × `height` cells, keeping the largest value
+/// of each block of cells that becomes one.
+#[must_use]
+pub fn shrink(grid: &Grid<u32>, width: usize, height: usize) -> Grid<u32> {
+  if grid.width() == 0 || width == 0 || height == 0 {
+    return Grid::default();
+  }
+  let step = grid.width().div_ceil(width).max(grid.height().div_ceil(height));
+
+  let mut shrunk = Grid::new(grid.width().div_ceil(step), grid.height().div_ceil(step), 0);
+  let step = i32::try_from(step).expect("a grid that fits in memory");
+  for (point, &value) in grid.iter() {
+    let cell = &mut shrunk[Point::new(point.x / step, point.y / step)];
+    *cell = (*cell).max(value);
+  }
+
+  shrunk
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn draw_stacks_like_the_puzzle() {
+    let Some(Ok(Visual::Text(lines))) = visualize(5, &aoc_runner::workspace_root().join("day-05").join("example.txt")) else {
+      panic!("no stacks");
+    };
+    assert_eq!(lines[..6], ["CrateMover 9000", "        [Z]", "        [N]", "        [D]", "[C] [M] [P]", " 1   2   3"]);
+  }
+
+  #[test]
+  fn crt_rows() {
+    let Some(Ok(Visual::Text(lines))) = visualize(10, &aoc_runner::workspace_root().join("day-10").join("example.txt")) else {
+      panic!("no CRT");
+    };
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].starts_with("██  ██  ██  "), "{lines:?}");
+  }
+
+  #[test]
+  fn no_picture_for_other_days() {
+    assert!(visualize(1, Path::new("input.txt")).is_none());
+    assert_eq!(title(1), None);
+  }
+
+  #[test]
+  fn shrink_keeps_largest() {
+    let grid = Grid::from_rows([vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![0, 0, 0, 9]]).unwrap();
+    assert_eq!(shrink(&grid, 2, 2), Grid::from_rows([vec![6, 8], vec![0, 9]]).unwrap());
+    assert_eq!(shrink(&grid, 10, 10), grid);
+    assert_eq!(shrink(&grid, 0, 2).height(), 0);
+  }
+}
@@ -0,0 +1,228 @@
+use std::{
+  env,
+  ffi::OsStr,
+  io,
+  path::{self, Path},
+  process::{Command, Output},
+  sync::mpsc,
+  time::Duration,
+};
+
+use aoc_runner::{
+  registry::{same_answer, Expected, Registry},
+  workspace_root, Day,
+};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Deserialize;
+
+// editors write a file in several steps, wait for them to finish before running
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// The answers of one run of a day's binary, read from its `--format json` line.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Solved {
+  #[serde(rename = "part1")]
+  pub part_one: String,
+  #[serde(rename = "part2")]
+  pub part_two: String,
+  parse_ns: u64,
+  part1_ns: u64,
+  part2_ns: u64,
+}
+
+impl Solved {
+  fn time(&self) -> Duration {
+    Duration::from_nanos(self.parse_ns + self.part1_ns + self.part2_ns)
+  }
+}
+
+/// Runs the day's example tests and its solver on `input`, then again every
+/// time something in the day's `src/`, its directory or `input` changes, until
+/// the watcher stops.
+///
+/// Both run through cargo, so they see the code as it is on disk rather than as
+/// it was when `aoc` was built.
+///
+/// # Errors
+///
+/// Returns an error if the files cannot be watched.
+pub fn watch(day: &Day, input: &Path) -> notify::Result<()> {
+  let directory = day.directory();
+  let input = path::absolute(input)?;
+  let (sender, events) = mpsc::channel();
+  let mut watcher = notify::recommended_watcher(sender)?;
+  watcher.watch(&directory.join("src"), RecursiveMode::Recursive)?;
+  watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+  // the directory rather than the file, which editors replace on save
+  if let Some(parent) = input.parent().filter(|parent| !parent.starts_with(&directory)) {
+    watcher.watch(parent, RecursiveMode::NonRecursive)?;
+  }
+
+  let mut previous = None;
+  loop {
+    run(day, &input, &mut previous);
+    println!("watching {} for changes", directory.display());
+
+    loop {
+      match events.recv() {
+        Ok(Ok(event)) if is_relevant(&event, &directory, &input) => break,
+        Ok(Ok(_)) => {}
+        Ok(Err(error)) => return Err(error),
+        Err(mpsc::RecvError) => return Ok(()),
+      }
+    }
+    while events.recv_timeout(SETTLE).is_ok() {}
+  }
+}
+
+fn run(day: &Day, input: &Path, previous: &mut Option<Solved>) {
+  let package = format!("day-{:02}", day.number);
+  println!("\nday {:02}: running example tests", day.number);
+  match cargo(&["test", "--quiet", "-p", &package, "--", "example"]) {
+    Ok(output) if output.status.success() => println!("tests: ok"),
+    Ok(output) => println!("tests: failed\n{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)),
+    Err(error) => println!("tests: cannot run cargo: {error}"),
+  }
+
+  println!("day {:02}: solving {}", day.number, input.display());
+  let arguments = [OsStr::new("run"), "--release".as_ref(), "--quiet".as_ref(), "-p".as_ref(), package.as_ref(), "--".as_ref(), "--format".as_ref(), "json".as_ref(), input.as_os_str()];
+  let solved = match cargo(&arguments) {
+    Ok(output) if output.status.success() => {
+      let stdout = String::from_utf8_lossy(&output.stdout);
+      match serde_json::from_str::<Solved>(stdout.lines().last().unwrap_or_default()) {
+        Ok(solved) => solved,
+        Err(error) => {
+          println!("solver: unexpected output: {error}\n{stdout}");
+          return;
+        }
+      }
+    }
+    Ok(output) => {
+      println!("solver: failed\n{}", String::from_utf8_lossy(&output.stderr));
+      return;
+    }
+    Err(error) => {
+      println!("solver: cannot run cargo: {error}");
+      return;
+    }
+  };
+
+  let registry = Registry::load();
+  let name = input.strip_prefix(day.directory()).ok().and_then(Path::to_str);
+  let expected = match (&registry, name) {
+    (Ok(registry), Some(name)) => registry.expected(day.number, name),
+    _ => None,
+  };
+  if let Err(error) = &registry {
+    println!("answers: {error}");
+  }
+
+  println!("{}", report(&solved, previous.as_ref(), expected));
+  *previous = Some(solved);
+}
+
+fn cargo(arguments: &[impl AsRef<OsStr>]) -> io::Result<Output> {
+  let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+  Command::new(cargo).args(arguments).current_dir(workspace_root()).output()
+}
+
+// changes to the code, the fixtures next to it or the input, but not reads or
+// the hidden and backup files editors leave around
+fn is_relevant(event: &Event, directory: &Path, input: &Path) -> bool {
+  !matches!(event.kind, EventKind::Access(_)) && event.paths.iter().any(|path| {
+    let hidden = path.file_name().and_then(OsStr::to_str).is_none_or(|name| name.starts_with('.') || name.ends_with('~'));
+    !hidden && (path == input || path.starts_with(directory.join("src")) || path.parent() == Some(directory))
+  })
+}
+
+/// Each answer, how it changed since the previous run and whether it matches
+/// `answers.toml`, e.g. `part one: 1820 (was 1794, correct)`.
+pub fn report(solved: &Solved, previous: Option<&Solved>, expected: Option<&Expected>) -> String {
+  let parts = [
+    ("part one", &solved.part_one, previous.map(|previous| &previous.part_one), expected.and_then(|expected| expected.part_one.as_ref())),
+    ("part two", &solved.part_two, previous.map(|previous| &previous.part_two), expected.and_then(|expected| expected.part_two.as_ref())),
+  ];
+
+  let mut lines = vec![];
+  for (name, answer, previous, expected) in parts {
+    let multi_line = answer.contains('\n');
+    let change = match previous {
+      Some(previous) if same_answer(previous, answer) => Some(String::from("unchanged")),
+      Some(_) if multi_line => Some(String::from("changed")),
+      Some(previous) => Some(format!("was {previous}")),
+      None => None,
+    };
+    let verdict = match expected {
+      Some(expected) if same_answer(expected, answer) => String::from("correct"),
+      Some(expected) if multi_line || expected.contains('\n') => String::from("wrong"),
+      Some(expected) => format!("expected {expected}"),
+      None => String::from("not in answers.toml"),
+    };
+
+    let notes = change.into_iter().chain([verdict]).collect::<Vec<_>>().join(", ");
+    if multi_line {
+      lines.push(format!("{name} ({notes}):\n{answer}"));
+    } else {
+      lines.push(format!("{name}: {answer} ({notes})"));
+    }
+  }
+  lines.push(format!("time: {:.2?}", solved.time()));
+
+  lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use notify::event::{AccessKind, ModifyKind};
+
+  use super::*;
+
+  fn solved(part_one: &str, part_two: &str) -> Solved {
+    Solved { part_one: part_one.to_string(), part_two: part_two.to_string(), parse_ns: 1000, part1_ns: 2000, part2_ns: 0 }
+  }
+
+  fn expected(part_one: Option<&str>, part_two: Option<&str>) -> Expected {
+    Expected { part_one: part_one.map(String::from), part_two: part_two.map(String::from) }
+  }
+
+  #[test]
+  fn report_first_run_against_registry() {
+    let report = report(&solved("21", "8"), None, Some(&expected(Some("21"), None)));
+    assert_eq!(report, "part one: 21 (correct)\npart two: 8 (not in answers.toml)\ntime: 3.00µs");
+  }
+
+  #[test]
+  fn report_changes_since_previous_run() {
+    let report = report(&solved("21", "9"), Some(&solved("21", "8")), Some(&expected(Some("21"), Some("8"))));
+    assert_eq!(report, "part one: 21 (unchanged, correct)\npart two: 9 (was 8, expected 8)\ntime: 3.00µs");
+  }
+
+  #[test]
+  fn report_multi_line_answers_on_their_own_lines() {
+    let report = report(&solved("13140", "█ █\n ██"), Some(&solved("13140", "█ █\n██")), Some(&expected(None, Some("\n█ █\n ██\n"))));
+    assert_eq!(report, "part one: 13140 (unchanged, not in answers.toml)\npart two (changed, correct):\n█ █\n ██\ntime: 3.00µs");
+  }
+
+  #[test]
+  fn solved_from_runner_json() {
+    let json = r#"{"day":8,"part1":"21","part2":"8","parse_ns":1000,"part1_ns":2000,"part2_ns":0,"parse_heap_bytes":512,"part1_heap_bytes":0,"part2_heap_bytes":0}"#;
+    assert_eq!(serde_json::from_str::<Solved>(json).unwrap(), solved("21", "8"));
+  }
+
+  #[test]
+  fn relevant_changes() {
+    let directory = PathBuf::from("/aoc/day-08");
+    let input = PathBuf::from("/inputs/day-08.txt");
+    let event = |kind, path: &str| Event::new(kind).add_path(PathBuf::from(path));
+    let modify = EventKind::Modify(ModifyKind::Any);
+
+    assert!(is_relevant(&event(modify, "/aoc/day-08/src/lib.rs"), &directory, &input));
+    assert!(is_relevant(&event(modify, "/aoc/day-08/example.txt"), &directory, &input));
+    assert!(is_relevant(&event(modify, "/inputs/day-08.txt"), &directory, &input));
+    assert!(!is_relevant(&event(modify, "/inputs/day-09.txt"), &directory, &input));
+    assert!(!is_relevant(&event(modify, "/aoc/day-08/src/.lib.rs.swp"), &directory, &input));
+    assert!(!is_relevant(&event(EventKind::Access(AccessKind::Any), "/aoc/day-08/src/lib.rs"), &directory, &input));
+  }
+}
@@ -0,0 +1,25 @@
+use std::fmt::Write;
+
+use aoc_runner::Rng;
+
+/// A calorie list roughly `scale` times the size of a real puzzle input.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  calorie_list(&mut Rng::new(seed), 250 * scale)
+}
+
+/// A calorie list for `elves` elves carrying 1 to 15 items each.
+pub fn calorie_list(rng: &mut Rng, elves: usize) -> String {
+  let mut input = String::new();
+  for elf in 0..elves {
+    if elf > 0 {
+      input.push('\n');
+    }
+
+    for _ in 0..rng.range(1..=15) {
+      writeln!(input, "{}", rng.range(1000..=60_000)).unwrap();
+    }
+  }
+
+  input
+}
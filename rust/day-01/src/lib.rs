@@ -1,75 +1,466 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  cmp::Reverse,
+  collections::{BTreeMap, BinaryHeap},
+  io::BufRead,
   path::Path,
 };
+use aoc_runner::{
+  parsing::{self, Span},
+  DayError, Solution,
+};
+
+pub mod generator;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod mapped;
+pub mod stats;
+
+pub use stats::CalorieStats;
+
+/// One thing an elf carries: its calories, and what it is if the input says,
+/// e.g. `apple 3000`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+  pub label: Option<String>,
+  pub calories: u64,
+}
+
+impl Item {
+  #[must_use]
+  pub fn new(calories: u64) -> Self {
+    Item { label: None, calories }
+  }
+
+  #[must_use]
+  pub fn labelled(label: impl Into<String>, calories: u64) -> Self {
+    Item { label: Some(label.into()), calories }
+  }
+}
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct ElfFoodBag(Vec<u32>);
+/// The items one elf carries, with where the elf came in the input, counting
+/// from 0, and its name if its bag starts with a header like `Alice:`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ElfFoodBag {
+  index: usize,
+  name: Option<String>,
+  items: Vec<Item>,
+  total: u64,
+}
 
 impl ElfFoodBag {
-  pub fn new() -> ElfFoodBag {
-    return ElfFoodBag(Vec::new());
+  #[must_use]
+  pub fn new(index: usize) -> ElfFoodBag {
+    ElfFoodBag { index, name: None, items: Vec::new(), total: 0 }
+  }
+
+  #[must_use]
+  pub fn named(index: usize, name: impl Into<String>) -> ElfFoodBag {
+    ElfFoodBag { name: Some(name.into()), ..ElfFoodBag::new(index) }
+  }
+
+  /// Adds `item` to the bag and returns the bag's new total, or leaves the bag
+  /// as it was and returns `None` if the total would not fit in a `u64`.
+  #[must_use]
+  pub fn insert(&mut self, item: Item) -> Option<u64> {
+    self.total = self.total.checked_add(item.calories)?;
+    self.items.push(item);
+    Some(self.total)
+  }
+
+  #[must_use]
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  #[must_use]
+  pub fn name(&self) -> Option<&str> {
+    self.name.as_deref()
+  }
+
+  /// Who carries the bag: its name, or `elf 4` for the fourth elf.
+  #[must_use]
+  pub fn elf(&self) -> String {
+    self.name.clone().unwrap_or_else(|| format!("elf {}", self.index + 1))
+  }
+
+  #[must_use]
+  pub fn items(&self) -> &[Item] {
+    &self.items
   }
 
-  pub fn insert(&mut self, item: u32) {
-    self.0.push(item);
+  #[must_use]
+  pub fn get_total_number_of_calories(&self) -> u64 {
+    self.total
   }
 
-  pub fn get_total_number_of_calories(&self) -> u32 {
-    return self.0.iter().sum();
+  /// The calories of the items labelled `label`, which are some of the total
+  /// and so fit in a `u64` too.
+  #[must_use]
+  pub fn calories_of(&self, label: &str) -> u64 {
+    self.items.iter().filter(|item| item.label.as_deref() == Some(label)).map(|item| item.calories).sum()
   }
 }
 
-impl Ord for ElfFoodBag {
-  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-    return self.get_total_number_of_calories().cmp(&other.get_total_number_of_calories());
+/// One elf on a [`Leaderboard`]: where its bag came in the input, counting from
+/// 0, and how many calories it carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Standing {
+  pub index: usize,
+  pub total: u64,
+}
+
+/// The `n` elves carrying the most calories out of all the elves pushed so far,
+/// kept in a min-heap of at most `n` entries so each elf costs `O(log n)`. Of
+/// elves carrying the same, the one that came first stays.
+#[derive(Debug, Clone)]
+pub struct Leaderboard {
+  size: usize,
+  // the weakest standing on top, to be pushed out first
+  heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+}
+
+impl Leaderboard {
+  #[must_use]
+  pub fn new(size: usize) -> Self {
+    Leaderboard { size, heap: BinaryHeap::with_capacity(size + 1) }
+  }
+
+  pub fn push(&mut self, index: usize, total: u64) {
+    let entry = Reverse((total, Reverse(index)));
+    if self.heap.len() < self.size {
+      self.heap.push(entry);
+    } else if self.heap.peek().is_some_and(|weakest| entry < *weakest) {
+      // `Reverse` flips the order, so `entry` beats the weakest standing
+      self.heap.pop();
+      self.heap.push(entry);
+    }
+  }
+
+  /// The elves on the board, carrying the most first.
+  #[must_use]
+  pub fn standings(&self) -> Vec<Standing> {
+    let mut standings = self.heap.iter().map(|&Reverse((total, Reverse(index)))| Standing { index, total }).collect::<Vec<_>>();
+    standings.sort_unstable_by_key(|standing| (Reverse(standing.total), standing.index));
+    standings
   }
 }
 
-impl PartialOrd for ElfFoodBag {
-  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-    return Some(self.cmp(other));
+/// The `n` elves carrying the most calories, carrying the most first, without
+/// sorting every bag.
+#[must_use]
+pub fn top_n(bags: &[ElfFoodBag], n: usize) -> Vec<Standing> {
+  let mut leaderboard = Leaderboard::new(n);
+  for bag in bags {
+    leaderboard.push(bag.index, bag.get_total_number_of_calories());
   }
+
+  leaderboard.standings()
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> (u32, u32) {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
-  let mut bags = Vec::new();
-  let mut bag = ElfFoodBag::new();
-  for line in reader.lines() {
-    let line = line.expect("could not parse line");
+/// The calories of every labelled item, added up per label across all elves,
+/// which can be more than fits in a `u64`.
+#[must_use]
+pub fn calories_by_label(bags: &[ElfFoodBag]) -> BTreeMap<&str, u128> {
+  let mut calories = BTreeMap::new();
+  for item in bags.iter().flat_map(|bag| &bag.items) {
+    if let Some(label) = &item.label {
+      *calories.entry(label.as_str()).or_default() += u128::from(item.calories);
+    }
+  }
+
+  calories
+}
+
+/// The elf carrying the most calories of items labelled `label`, and how many,
+/// the first of them if several carry the same, or `None` if nobody carries
+/// any such item.
+#[must_use]
+pub fn most_of<'a>(bags: &'a [ElfFoodBag], label: &str) -> Option<(&'a ElfFoodBag, u64)> {
+  let carrying = bags.iter().filter(|bag| bag.items.iter().any(|item| item.label.as_deref() == Some(label)));
+  carrying.map(|bag| (bag, bag.calories_of(label))).fold(None, |most, (bag, calories)| match most {
+    Some((_, most_calories)) if most_calories >= calories => most,
+    _ => Some((bag, calories)),
+  })
+}
+
+// an optional `Alice:` header at the top of a bag names the elf
+fn header(line: &str) -> Option<&str> {
+  line.ends_with(':').then(|| line.trim_end_matches(':').trim())
+}
+
+// one item of a bag as its label, if any, and its calories, with the span of
+// the calories to blame if they are too many
+fn item(span: Span<'_>) -> Result<(Option<&str>, u64, Span<'_>), DayError> {
+  // the calories come last, after what the item is, e.g. `dried apple 3000`
+  let count = span.split(" ").last().unwrap_or(span);
+  let calories = count.parse::<u64>("invalid calorie count")?;
+  let label = span.as_str().rsplit_once(' ').map(|(label, _)| label.trim()).filter(|label| !label.is_empty());
+
+  Ok((label, calories, count))
+}
+
+// every bag is ranked by its total, so no bag may hold more than fits in a `u64`
+fn too_many_calories(count: Span<'_>) -> DayError {
+  count.error("too many calories for one elf")
+}
+
+// what one line of a calorie list says
+enum Entry<'a> {
+  // a blank line, ending the bag
+  End,
+  // the header naming the elf, which may leave the name empty
+  Name(&'a str),
+  Item(Option<&'a str>, u64, Span<'a>),
+}
+
+// tells what each line of a calorie list says, fed the lines in order, so
+// every parser reads them the same
+#[derive(Debug, Default)]
+struct BagReader {
+  started: bool,
+}
+
+impl BagReader {
+  fn read<'a>(&mut self, line: Span<'a>) -> Result<Entry<'a>, DayError> {
     if line.is_empty() {
-      bags.push(bag);
-      bag = ElfFoodBag::new();
-    } else {
-      let calories = line.split(" ").last().unwrap().parse::<u32>().unwrap();
-      bag.insert(calories);
+      self.started = false;
+      return Ok(Entry::End);
+    }
+
+    let started = std::mem::replace(&mut self.started, true);
+    match header(line.as_str()) {
+      Some(name) if !started => Ok(Entry::Name(name)),
+      _ => {
+        let (label, calories, count) = item(line)?;
+        Ok(Entry::Item(label, calories, count))
+      }
     }
   }
-  // add the last bag
+}
+
+fn parse(reader: impl BufRead) -> Result<Vec<ElfFoodBag>, DayError> {
+  let mut bags = Vec::new();
+  let (mut bag, mut bag_reader) = (ElfFoodBag::new(0), BagReader::default());
+  for line in parsing::lines(reader) {
+    let line = line?;
+    match bag_reader.read(line.span())? {
+      Entry::End => {
+        let next = ElfFoodBag::new(bag.index + 1);
+        bags.push(std::mem::replace(&mut bag, next));
+      }
+      Entry::Name(name) if !name.is_empty() => bag.name = Some(name.to_string()),
+      Entry::Name(_) => {}
+      Entry::Item(label, calories, count) => {
+        bag.insert(Item { label: label.map(String::from), calories }).ok_or_else(|| too_many_calories(count))?;
+      }
+    }
+  }
+  // a blank line ends each bag, so there is one more bag than blank lines
   bags.push(bag);
-  bags.sort();
 
-  let max_calories_in_a_bag = bags.iter().max().unwrap().get_total_number_of_calories();
-  let top_three_bags_total = bags.iter().rev().take(3).map(|bag| bag.get_total_number_of_calories()).sum::<u32>();
+  Ok(bags)
+}
 
-  (max_calories_in_a_bag, top_three_bags_total)
+// part one from the standings at the top of a leaderboard
+fn most(standings: &[Standing]) -> Result<u64, DayError> {
+  standings.first().map(|standing| standing.total).ok_or_else(|| DayError::unsolvable("no elves"))
+}
+
+// part two from the standings at the top of a leaderboard
+fn top_three(standings: &[Standing]) -> Result<u64, DayError> {
+  let mut totals = standings.iter().take(3).map(|standing| standing.total);
+  totals.try_fold(0_u64, u64::checked_add).ok_or_else(|| DayError::unsolvable("the top three elves carry more calories than fit in a `u64`"))
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+  const DAY: u8 = 1;
+
+  type Input = Vec<ElfFoodBag>;
+  type PartOne = u64;
+  type PartTwo = u64;
+
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
+  }
+
+  fn part_one(bags: &Self::Input) -> Result<u64, DayError> {
+    most(&top_n(bags, 1))
+  }
+
+  fn part_two(bags: &Self::Input) -> Result<u64, DayError> {
+    top_three(&top_n(bags, 3))
+  }
+}
+
+/// # Errors
+///
+/// Returns an error if the file cannot be read or has a malformed calorie count.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(u64, u64), DayError> {
+  aoc_runner::solve::<Day01>(filename.as_ref())
+}
+
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str(input: &str) -> Result<(u64, u64), DayError> {
+  aoc_runner::solve_str::<Day01>(input)
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader(reader: impl BufRead) -> Result<(u64, u64), DayError> {
+  aoc_runner::solve_reader::<Day01>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
   fn solve_first_example() {
-    assert_eq!(solve("example.txt"), (24000, 45000));
+    assert_eq!(solve("example.txt").unwrap(), (24_000, 45_000));
+  }
+
+  #[test]
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day01>();
+  }
+
+  #[test]
+  fn solve_malformed() {
+    let error = solve("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:6:1: invalid calorie count: `4OOO`");
+  }
+
+  #[test]
+  fn solve_beyond_u32() {
+    assert_eq!(solve_str("4000000000\n\n4000000000\n4000000000\n").unwrap(), (8_000_000_000, 12_000_000_000));
+    let bags = Day01::parse("18446744073709551615\n\n1\n".as_bytes()).unwrap();
+    assert_eq!(bags[0].get_total_number_of_calories(), u64::MAX);
+  }
+
+  #[test]
+  fn solve_too_many_calories() {
+    let error = solve_str("10000000000000000000\n\n10000000000000000000\n10000000000000000000\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 4, column 1: too many calories for one elf: `10000000000000000000`");
+    let error = solve_str("10000000000000000000\n\n10000000000000000000\n").unwrap_err();
+    assert_eq!(error.to_string(), "the top three elves carry more calories than fit in a `u64`");
+    let error = solve_str("18446744073709551616\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 1, column 1: invalid calorie count: `18446744073709551616`");
+  }
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str("1000\n2000\n\n4000\n\n5000\n6000\n\n7000\n").unwrap(), (11_000, 22_000));
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), (66186, 196804));
+  fn top_of_example() {
+    let bags = Day01::parse(include_str!("../example.txt").as_bytes()).unwrap();
+    let top = top_n(&bags, 3).iter().map(|standing| (standing.index, standing.total)).collect::<Vec<_>>();
+    assert_eq!(top, [(3, 24_000), (2, 11_000), (4, 10_000)]);
+    assert_eq!(top_n(&bags, 10).len(), 5);
+    assert!(top_n(&bags, 0).is_empty());
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn leaderboard_keeps_first_of_ties() {
+    let mut leaderboard = Leaderboard::new(2);
+    for (index, total) in [5, 7, 5, 7, 9].into_iter().enumerate() {
+      leaderboard.push(index, total);
+    }
+    assert_eq!(leaderboard.standings(), [Standing { index: 4, total: 9 }, Standing { index: 1, total: 7 }]);
+  }
+
+  const LABELLED: &str = "Alice:\napple 3000\ndried apple 2000\n1000\n\napple 4000\n\nCarol:\napple 2000\napple 2000\nbanana 500\n";
+
+  #[test]
+  fn labelled_items() {
+    let bags = Day01::parse(LABELLED.as_bytes()).unwrap();
+    assert_eq!(bags.iter().map(ElfFoodBag::elf).collect::<Vec<_>>(), ["Alice", "elf 2", "Carol"]);
+    assert_eq!(bags[0].items(), [Item::labelled("apple", 3000), Item::labelled("dried apple", 2000), Item::new(1000)]);
+    assert_eq!(solve_str(LABELLED).unwrap(), (6000, 14_500));
+  }
+
+  #[test]
+  fn label_queries() {
+    let bags = Day01::parse(LABELLED.as_bytes()).unwrap();
+    assert_eq!(calories_by_label(&bags).into_iter().collect::<Vec<_>>(), [("apple", 11_000), ("banana", 500), ("dried apple", 2000)]);
+    // Carol's two apples tie with the second elf's one, who came first
+    assert_eq!(most_of(&bags, "apple").map(|(bag, calories)| (bag.elf(), calories)), Some((String::from("elf 2"), 4000)));
+    assert_eq!(most_of(&bags, "banana").map(|(bag, calories)| (bag.elf(), calories)), Some((String::from("Carol"), 500)));
+    assert!(most_of(&bags, "cherry").is_none());
+  }
+
+  fn bag_of(index: usize, name: Option<String>, items: Vec<Item>) -> ElfFoodBag {
+    let mut bag = ElfFoodBag { name, ..ElfFoodBag::new(index) };
+    for item in items {
+      bag.insert(item).unwrap();
+    }
+    bag
+  }
+
+  #[test]
+  fn insert_beyond_u64() {
+    let mut bag = ElfFoodBag::new(0);
+    assert_eq!(bag.insert(Item::new(u64::MAX - 1)), Some(u64::MAX - 1));
+    assert_eq!(bag.insert(Item::labelled("apple", 2)), None);
+    assert_eq!(bag.insert(Item::new(1)), Some(u64::MAX));
+    assert_eq!((bag.items().len(), bag.get_total_number_of_calories()), (2, u64::MAX));
+  }
+
+  fn render_bags(bags: &[ElfFoodBag]) -> String {
+    let bags = bags.iter().map(|bag| {
+      let header = bag.name().map(|name| format!("{name}:"));
+      let items = bag.items().iter().map(|item| match &item.label {
+        Some(label) => format!("{label} {}", item.calories),
+        None => item.calories.to_string(),
+      });
+      header.into_iter().chain(items).collect::<Vec<_>>().join("\n")
+    });
+    bags.collect::<Vec<_>>().join("\n\n") + "\n"
+  }
+
+  fn bags() -> impl Strategy<Value = Vec<ElfFoodBag>> {
+    let item = (prop::option::of("[a-z]{1,8}( [a-z]{1,8})?"), 0..100_000_u64).prop_map(|(label, calories)| Item { label, calories });
+    let bag = (prop::option::of("[A-Z][a-z]{0,8}"), prop::collection::vec(item, 1..10));
+    prop::collection::vec(bag, 1..20).prop_map(|bags| {
+      let bags = bags.into_iter().enumerate().map(|(index, (name, items))| bag_of(index, name, items));
+      bags.collect()
+    })
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip(bags in bags()) {
+      prop_assert_eq!(Day01::parse(render_bags(&bags).as_bytes())?, bags);
+    }
+
+    #[test]
+    fn totals_beyond_u32(bags in prop::collection::vec(prop::collection::vec(u64::from(u32::MAX)..u64::MAX / 64, 1..10), 1..20)) {
+      let input = bags.iter().map(|bag| bag.iter().map(u64::to_string).collect::<Vec<_>>().join("\n")).collect::<Vec<_>>().join("\n\n");
+      let mut totals = bags.iter().map(|bag| bag.iter().sum::<u64>()).collect::<Vec<_>>();
+      totals.sort_unstable_by_key(|&total| Reverse(total));
+      prop_assert_eq!(solve_str(&input)?, (totals[0], totals.iter().take(3).sum()));
+    }
+
+    #[test]
+    fn top_n_like_sorting(totals in prop::collection::vec(0..20_u64, 0..50), n in 0..10_usize) {
+      let bags = totals.iter().enumerate().map(|(index, &total)| bag_of(index, None, vec![Item::new(total)])).collect::<Vec<_>>();
+      let mut sorted = totals.iter().enumerate().map(|(index, &total)| Standing { index, total }).collect::<Vec<_>>();
+      sorted.sort_by_key(|standing| (Reverse(standing.total), standing.index));
+      sorted.truncate(n);
+      prop_assert_eq!(top_n(&bags, n), sorted);
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day01::parse(input.as_bytes());
+    }
+  }
+}
@@ -1,14 +1,108 @@
-use std::env;
-use std::time::Instant;
+#[global_allocator]
+static ALLOCATOR: aoc_runner::alloc::CountingAllocator = aoc_runner::alloc::CountingAllocator;
+
+use std::{env, path::Path, process, time::Instant};
+
+use aoc_runner::Solution;
+use day_01::{CalorieStats, Day01, ElfFoodBag};
+
+const USAGE: &str = "usage: day-01 [--top N | --labels | --elves | stats | --mapped [--jobs N]] <input file | ->";
+
+// the modes only day 1 has, each first on the command line, so anything else
+// is the runner's; `--stats` is the flag spelling of the `stats` report
+const MODES: [&str; 6] = ["--top", "--labels", "--elves", "stats", "--stats", "--mapped"];
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let filename = &args[1];
-
-    let time = Instant::now();
-    println!(
-        "answer: {:?}, time: {:.2?}",
-        day_01::solve(filename),
-        time.elapsed()
-    );
-}
\ No newline at end of file
+  match env::args().skip(1).collect::<Vec<_>>().as_slice() {
+    [flag, n, input] if flag == "--top" => top(n, input),
+    [flag, input] if flag == "--labels" => labels(input),
+    [flag, input] if flag == "--elves" => elves(input),
+    [command, input] if command == "stats" || command == "--stats" => stats(input),
+    [flag, input] if flag == "--mapped" => mapped(None, input),
+    [flag, jobs_flag, jobs, input] if flag == "--mapped" && jobs_flag == "--jobs" => mapped(Some(jobs), input),
+    [flag, ..] if MODES.contains(&flag.as_str()) => usage(),
+    _ => aoc_runner::main::<Day01>(),
+  }
+}
+
+fn usage() -> ! {
+  eprintln!("{USAGE}");
+  process::exit(2);
+}
+
+fn bags(input: &str) -> Vec<ElfFoodBag> {
+  let input = Path::new(input);
+  let bags = aoc_runner::open(input).and_then(|reader| Day01::parse(reader).map_err(|error| error.in_file(input)));
+  bags.unwrap_or_else(|error| {
+    eprintln!("error: {error}");
+    process::exit(1);
+  })
+}
+
+// `day-01 --top N <input file | ->`: the N elves carrying the most calories,
+// by name or numbered from 1 in the order they come in the input
+fn top(n: &str, input: &str) {
+  let Ok(n) = n.parse::<usize>() else { usage() };
+  let bags = bags(input);
+
+  let standings = day_01::top_n(&bags, n);
+  let width = standings.len().to_string().len();
+  for (rank, standing) in (1..).zip(&standings) {
+    println!("{rank:>width$}. {}: {}", bags[standing.index].elf(), standing.total);
+  }
+}
+
+// `day-01 --labels <input file | ->`: the calories of each kind of item, and
+// who carries the most of it
+fn labels(input: &str) {
+  let bags = bags(input);
+  for (label, calories) in day_01::calories_by_label(&bags) {
+    match day_01::most_of(&bags, label) {
+      Some((bag, most)) => println!("{label}: {calories}, most carried by {} ({most})", bag.elf()),
+      None => println!("{label}: {calories}"),
+    }
+  }
+}
+
+// `day-01 --elves <input file | ->`: every elf's total and items
+fn elves(input: &str) {
+  for bag in bags(input) {
+    println!("{}: {}", bag.elf(), bag.get_total_number_of_calories());
+    for item in bag.items() {
+      match &item.label {
+        Some(label) => println!("  {label} {}", item.calories),
+        None => println!("  {}", item.calories),
+      }
+    }
+  }
+}
+
+// `day-01 stats <input file | ->`: how the calories are spread over the elves
+fn stats(input: &str) {
+  match CalorieStats::of(&bags(input)) {
+    Some(stats) => print!("{stats}"),
+    None => println!("no elves"),
+  }
+}
+
+// `day-01 --mapped [--jobs N] <input file | ->`: both parts without keeping
+// the bags, for calorie lists too big to hold
+fn mapped(jobs: Option<&str>, input: &str) {
+  let jobs = match jobs.map(str::parse::<usize>) {
+    None => aoc_runner::batch::default_jobs(),
+    Some(Ok(jobs)) if jobs > 0 => jobs,
+    Some(_) => usage(),
+  };
+
+  let started = Instant::now();
+  match day_01::mapped::solve(Path::new(input), jobs) {
+    Ok((part_one, part_two)) => {
+      let threads = if jobs == 1 { "thread" } else { "threads" };
+      println!("day 01: part one: {part_one}, part two: {part_two}, time: {:.2?} on {jobs} {threads}", started.elapsed());
+    }
+    Err(error) => {
+      eprintln!("error: {error}");
+      process::exit(1);
+    }
+  }
+}
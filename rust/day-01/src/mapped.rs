@@ -0,0 +1,308 @@
+//! Day 1 for calorie lists too big to hold as bags: the file is memory-mapped
+//! and cut into chunks at blank lines, each chunk's elves are ranked on a
+//! [`Leaderboard`] of their own by a pool of threads, and the leaderboards are
+//! merged, so no bag is ever kept. Stdin, which cannot be mapped, is ranked
+//! line by line as it comes instead.
+use std::{
+  fs::File,
+  io::{self, BufRead},
+  path::Path,
+  sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+  thread,
+};
+
+use aoc_runner::{
+  parsing::{self, Span},
+  DayError,
+};
+use memmap2::Mmap;
+
+use crate::{most, too_many_calories, top_three, BagReader, Entry, Leaderboard, Standing};
+
+// chunks smaller than this cost more in threads than they save
+const MIN_CHUNK: usize = 1 << 20;
+
+/// The `n` elves carrying the most calories in the file at `path`, `-` being
+/// stdin, with `jobs` threads ranking them.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or has a malformed calorie count.
+pub fn top_n(path: &Path, n: usize, jobs: usize) -> Result<Vec<Standing>, DayError> {
+  let read = || -> Result<Vec<Standing>, DayError> {
+    if path == Path::new("-") {
+      return top_n_streamed(io::stdin().lock(), n);
+    }
+
+    let file = File::open(path)?;
+    // SAFETY: the map is only read, and the file changing underneath it while it
+    // is being solved, which nothing here does, garbles the answer but cannot
+    // break the parser, which checks every line anyway
+    let map = unsafe { Mmap::map(&file)? };
+    top_n_of(&map, n, jobs)
+  };
+
+  read().map_err(|error| error.in_file(path))
+}
+
+/// Parts one and two for the file at `path` like [`crate::solve`], but without
+/// keeping any bags, with `jobs` threads.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or has a malformed calorie count.
+pub fn solve(path: &Path, jobs: usize) -> Result<(u64, u64), DayError> {
+  let standings = top_n(path, 3, jobs)?;
+  Ok((most(&standings)?, top_three(&standings)?))
+}
+
+/// The `n` elves carrying the most calories in `input`, with `jobs` threads
+/// ranking them.
+///
+/// # Errors
+///
+/// Returns an error if the input is not UTF-8 or has a malformed calorie count.
+pub fn top_n_of(input: &[u8], n: usize, jobs: usize) -> Result<Vec<Standing>, DayError> {
+  let jobs = jobs.max(1);
+  ranked(&chunks(input, (input.len() / (jobs * 4)).max(MIN_CHUNK)), n, jobs)
+}
+
+/// The `n` elves carrying the most calories in `reader`, ranked one line at a
+/// time on this thread.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or has a malformed calorie count.
+pub fn top_n_streamed(reader: impl BufRead, n: usize) -> Result<Vec<Standing>, DayError> {
+  let mut ranking = Ranking::new(n);
+  for line in parsing::lines(reader) {
+    ranking.read(line?.span())?;
+  }
+
+  Ok(ranking.finish().standings)
+}
+
+// `input` cut into chunks of about `size` bytes, each but the last ending with
+// a blank line, so no bag is split between two chunks
+fn chunks(input: &[u8], size: usize) -> Vec<&[u8]> {
+  let mut chunks = vec![];
+  let mut rest = input;
+  while rest.len() > size {
+    let Some(end) = blank_line_after(rest, size) else { break };
+    let (chunk, after) = rest.split_at(end);
+    chunks.push(chunk);
+    rest = after;
+  }
+  chunks.push(rest);
+
+  chunks
+}
+
+// the end of the first blank line, `\n` or `\r\n`, starting after `start`
+fn blank_line_after(input: &[u8], start: usize) -> Option<usize> {
+  let mut position = start;
+  while let Some(newline) = input[position..].iter().position(|&byte| byte == b'\n') {
+    let line_start = position + newline + 1;
+    match &input[line_start..] {
+      [b'\n', ..] => return Some(line_start + 1),
+      [b'\r', b'\n', ..] => return Some(line_start + 2),
+      _ => position = line_start,
+    }
+  }
+
+  None
+}
+
+// what ranking some lines found, with their elves and lines counted to number
+// the chunks after them
+struct Chunk {
+  elves: usize,
+  lines: usize,
+  standings: Vec<Standing>,
+}
+
+// the top `n` of some lines' bags, like `crate::parse` and `crate::top_n`
+// together but keeping only each bag's total, numbered from 0
+struct Ranking {
+  leaderboard: Leaderboard,
+  bag_reader: BagReader,
+  elves: usize,
+  lines: usize,
+  total: u64,
+}
+
+impl Ranking {
+  fn new(n: usize) -> Self {
+    Ranking { leaderboard: Leaderboard::new(n), bag_reader: BagReader::default(), elves: 0, lines: 0, total: 0 }
+  }
+
+  fn read(&mut self, line: Span<'_>) -> Result<(), DayError> {
+    self.lines += 1;
+    match self.bag_reader.read(line)? {
+      Entry::End => self.end_bag(),
+      Entry::Name(_) => {}
+      Entry::Item(_, calories, count) => self.total = self.total.checked_add(calories).ok_or_else(|| too_many_calories(count))?,
+    }
+
+    Ok(())
+  }
+
+  fn end_bag(&mut self) {
+    self.leaderboard.push(self.elves, self.total);
+    (self.elves, self.total) = (self.elves + 1, 0);
+  }
+
+  // the last bag has no blank line after it
+  fn finish(mut self) -> Chunk {
+    self.end_bag();
+    self.into_chunk()
+  }
+
+  fn into_chunk(self) -> Chunk {
+    Chunk { elves: self.elves, lines: self.lines, standings: self.leaderboard.standings() }
+  }
+}
+
+fn ranked(chunks: &[&[u8]], n: usize, jobs: usize) -> Result<Vec<Standing>, DayError> {
+  let next = AtomicUsize::new(0);
+  // chunks are taken in order, so after a failure the chunks before it are all
+  // still ranked to find the first error, and those after it can be left
+  let failed = AtomicBool::new(false);
+  let mut ranked = thread::scope(|scope| {
+    let workers = (0..jobs.min(chunks.len())).map(|_| scope.spawn(|| {
+      let mut ranked = vec![];
+      while !failed.load(Ordering::Relaxed) {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        let Some(chunk) = chunks.get(index) else { break };
+        let chunk = rank(chunk, index == chunks.len() - 1, n);
+        if chunk.is_err() {
+          failed.store(true, Ordering::Relaxed);
+        }
+        ranked.push((index, chunk));
+      }
+      ranked
+    })).collect::<Vec<_>>();
+
+    workers.into_iter().flat_map(|worker| worker.join().expect("ranking a chunk panicked")).collect::<Vec<_>>()
+  });
+  ranked.sort_by_key(|(index, _)| *index);
+
+  // chunks count their elves and lines from 0, so number them after the chunks before
+  let mut leaderboard = Leaderboard::new(n);
+  let (mut elves, mut lines) = (0, 0);
+  for (_, chunk) in ranked {
+    let chunk = chunk.map_err(|error| match error {
+      DayError::Parse { line, .. } => error.on_line(lines + line),
+      error => error,
+    })?;
+    for standing in chunk.standings {
+      leaderboard.push(elves + standing.index, standing.total);
+    }
+    elves += chunk.elves;
+    lines += chunk.lines;
+  }
+
+  Ok(leaderboard.standings())
+}
+
+// the lines of a chunk, split like `BufRead::lines` splits them: without the
+// `\n` or `\r\n` ending each one, and nothing after a `\n` at the very end
+fn lines(chunk: &[u8]) -> impl Iterator<Item = &[u8]> {
+  chunk.split_inclusive(|&byte| byte == b'\n').map(|line| match line.strip_suffix(b"\n") {
+    Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+    None => line,
+  })
+}
+
+fn rank(chunk: &[u8], last: bool, n: usize) -> Result<Chunk, DayError> {
+  let mut ranking = Ranking::new(n);
+  for (number, line) in (1..).zip(lines(chunk)) {
+    let line = std::str::from_utf8(line).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8"))?;
+    ranking.read(Span::new(line, number))?;
+  }
+
+  // every chunk but the last ends with a blank line, which ended its last bag
+  Ok(if last { ranking.finish() } else { ranking.into_chunk() })
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::prelude::*;
+
+  use super::*;
+  use crate::{generator, Day01};
+  use aoc_runner::Solution;
+
+  const LABELLED: &str = "Alice:\napple 3000\n1000\n\n\n4000\r\n\r\nBob:\r\n5000\r\n6000\r\n\n7000\n8000\n9000\n\n10000";
+
+  fn top_n_chunked(input: &str, n: usize, size: usize, jobs: usize) -> Result<Vec<Standing>, DayError> {
+    ranked(&chunks(input.as_bytes(), size), n, jobs)
+  }
+
+  #[test]
+  fn chunks_end_at_blank_lines() {
+    let chunks = chunks(LABELLED.as_bytes(), 1).into_iter().map(|chunk| std::str::from_utf8(chunk).unwrap()).collect::<Vec<_>>();
+    assert_eq!(chunks, ["Alice:\napple 3000\n1000\n\n", "\n4000\r\n\r\n", "Bob:\r\n5000\r\n6000\r\n\n", "7000\n8000\n9000\n\n", "10000"]);
+    assert_eq!(chunks.concat(), LABELLED);
+  }
+
+  #[test]
+  fn like_solving() {
+    let bags = Day01::parse(LABELLED.as_bytes()).unwrap();
+    for size in [1, 5, 20, 1000] {
+      for jobs in [1, 3] {
+        assert_eq!(top_n_chunked(LABELLED, 10, size, jobs).unwrap(), crate::top_n(&bags, 10), "chunks of {size}, {jobs} jobs");
+      }
+    }
+  }
+
+  #[test]
+  fn errors_on_their_line() {
+    let input = "1000\n\n2000\n\n3000\n\n4OOO\n";
+    for size in [1, 1000] {
+      let error = top_n_chunked(input, 3, size, 2).unwrap_err();
+      assert_eq!(error.to_string(), "line 7, column 1: invalid calorie count: `4OOO`");
+    }
+    let error = top_n(Path::new("malformed.txt"), 3, 2).unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:6:1: invalid calorie count: `4OOO`");
+  }
+
+  #[test]
+  fn solve_mapped() {
+    assert_eq!(solve(Path::new("example.txt"), 4).unwrap(), (24_000, 45_000));
+    assert_eq!(solve(Path::new("input.txt"), 4).unwrap(), crate::solve("input.txt").unwrap());
+  }
+
+  #[test]
+  fn lines_like_buf_read() {
+    for input in ["", "\n", "a\r\nb\r", "a\n\r\n\rb\n", "a\r\r\n"] {
+      let expected = input.as_bytes().lines().collect::<Result<Vec<_>, _>>().unwrap();
+      assert_eq!(lines(input.as_bytes()).map(|line| std::str::from_utf8(line).unwrap()).collect::<Vec<_>>(), expected, "{input:?}");
+    }
+  }
+
+  #[test]
+  fn streamed_like_solving() {
+    let bags = Day01::parse(LABELLED.as_bytes()).unwrap();
+    assert_eq!(top_n_streamed(LABELLED.as_bytes(), 10).unwrap(), crate::top_n(&bags, 10));
+    let error = top_n_streamed("1000\n\n4OOO\n".as_bytes(), 3).unwrap_err();
+    assert_eq!(error.to_string(), "line 3, column 1: invalid calorie count: `4OOO`");
+  }
+
+  #[test]
+  fn empty_file() {
+    let file = std::env::temp_dir().join(format!("aoc-test-day-01-empty-{}", std::process::id()));
+    std::fs::write(&file, "").unwrap();
+    assert_eq!(top_n(&file, 3, 2).unwrap(), [Standing { index: 0, total: 0 }]);
+    std::fs::remove_file(file).unwrap();
+  }
+
+  proptest! {
+    #[test]
+    fn chunked_like_solving(seed in any::<u64>(), size in 1..2000_usize, jobs in 1..5_usize, n in 0..10_usize) {
+      let input = generator::generate(seed, 1);
+      let bags = Day01::parse(input.as_bytes())?;
+      prop_assert_eq!(top_n_chunked(&input, n, size, jobs)?, crate::top_n(&bags, n));
+    }
+  }
+}
@@ -0,0 +1,184 @@
+use std::{
+  collections::BTreeMap,
+  fmt::{self, Display, Write},
+};
+
+use crate::ElfFoodBag;
+
+/// How the calories are spread over the elves, for reports and dashboards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalorieStats {
+  // every elf's total, smallest first
+  totals: Vec<u64>,
+  pub mean: f64,
+  pub median: f64,
+  /// The population standard deviation of the totals.
+  pub std_dev: f64,
+  /// The calories of the smallest and the largest single item, if any elf
+  /// carries anything.
+  pub min_item: Option<u64>,
+  pub max_item: Option<u64>,
+  /// How many elves carry each number of items.
+  pub items_per_elf: BTreeMap<usize, usize>,
+}
+
+// only totals past 2^53 lose their last digits as `f64`, which the statistics
+// can afford
+#[allow(clippy::cast_precision_loss)]
+fn float(n: u128) -> f64 {
+  n as f64
+}
+
+impl CalorieStats {
+  /// The statistics of every elf's total, or `None` if there are no elves.
+  #[must_use]
+  pub fn of(bags: &[ElfFoodBag]) -> Option<Self> {
+    let mut totals = bags.iter().map(ElfFoodBag::get_total_number_of_calories).collect::<Vec<_>>();
+    totals.sort_unstable();
+    let middle = totals.len() / 2;
+    let median = match totals.len() {
+      0 => return None,
+      len if len % 2 == 0 => f64::midpoint(float(totals[middle - 1].into()), float(totals[middle].into())),
+      _ => float(totals[middle].into()),
+    };
+
+    let elves = float(totals.len() as u128);
+    let mean = float(totals.iter().map(|&total| u128::from(total)).sum()) / elves;
+    let variance = totals.iter().map(|&total| (float(total.into()) - mean).powi(2)).sum::<f64>() / elves;
+    let items = bags.iter().flat_map(ElfFoodBag::items).map(|item| item.calories);
+    let mut items_per_elf = BTreeMap::new();
+    for bag in bags {
+      *items_per_elf.entry(bag.items().len()).or_default() += 1;
+    }
+
+    Some(CalorieStats { mean, median, std_dev: variance.sqrt(), min_item: items.clone().min(), max_item: items.max(), items_per_elf, totals })
+  }
+
+  #[must_use]
+  pub fn elves(&self) -> usize {
+    self.totals.len()
+  }
+
+  #[must_use]
+  pub fn min_total(&self) -> u64 {
+    self.totals[0]
+  }
+
+  #[must_use]
+  pub fn max_total(&self) -> u64 {
+    self.totals[self.totals.len() - 1]
+  }
+
+  /// The smallest total that at least `percent` percent of the elves carry no
+  /// more than, the nearest-rank percentile: 50 is the lower median, 100 the
+  /// largest total.
+  #[must_use]
+  pub fn percentile(&self, percent: u8) -> u64 {
+    let rank = (usize::from(percent.min(100)) * self.totals.len()).div_ceil(100);
+    self.totals[rank.max(1) - 1]
+  }
+
+  /// The totals counted in up to `buckets` ranges of the same size from the
+  /// smallest to the largest, fewer if the ranges cover the totals sooner, one
+  /// line per range with a bar of up to `width` `#`s.
+  #[must_use]
+  pub fn histogram(&self, buckets: usize, width: usize) -> String {
+    // in `u128`, where the last range may end past `u64::MAX`
+    let (min, max) = (u128::from(self.min_total()), u128::from(self.max_total()));
+    let size = (max - min) / buckets.max(1) as u128 + 1;
+    // rounding the size up can leave the last ranges past `max`
+    let buckets = usize::try_from((max - min) / size + 1).unwrap_or(buckets);
+    let mut counts = vec![0_usize; buckets];
+    for &total in &self.totals {
+      counts[usize::try_from((u128::from(total) - min) / size).unwrap_or(buckets - 1).min(buckets - 1)] += 1;
+    }
+
+    let most = counts.iter().copied().max().unwrap_or_default().max(1);
+    let digits = max.to_string().len();
+    let mut histogram = String::new();
+    for (bucket, &count) in (0_u128..).zip(&counts) {
+      let low = min + bucket * size;
+      let high = (low + size - 1).min(max);
+      let bar = "#".repeat((count * width).div_ceil(most));
+      writeln!(histogram, "{low:>digits$}-{high:<digits$} {bar} {count}").unwrap();
+    }
+
+    histogram
+  }
+}
+
+impl Display for CalorieStats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "elves: {}", self.elves())?;
+    writeln!(f, "totals: {} to {}", self.min_total(), self.max_total())?;
+    writeln!(f, "mean: {:.1}", self.mean)?;
+    writeln!(f, "median: {:.1}", self.median)?;
+    writeln!(f, "standard deviation: {:.1}", self.std_dev)?;
+    let percentiles = [10, 25, 50, 75, 90, 99].map(|percent| format!("p{percent} {}", self.percentile(percent)));
+    writeln!(f, "percentiles: {}", percentiles.join(", "))?;
+    match (self.min_item, self.max_item) {
+      (Some(min), Some(max)) => writeln!(f, "items: {min} to {max}")?,
+      _ => writeln!(f, "items: none")?,
+    }
+    let items_per_elf = self.items_per_elf.iter().map(|(items, elves)| format!("{items}: {elves}"));
+    writeln!(f, "items per elf: {}", items_per_elf.collect::<Vec<_>>().join(", "))?;
+    write!(f, "\n{}", self.histogram(10, 40))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::Day01;
+  use aoc_runner::Solution;
+
+  fn example() -> CalorieStats {
+    CalorieStats::of(&Day01::parse(include_str!("../example.txt").as_bytes()).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn stats_of_example() {
+    let stats = example();
+    assert_eq!((stats.elves(), stats.min_total(), stats.max_total()), (5, 4000, 24_000));
+    assert!((stats.mean - 11_000.0).abs() < 1e-9);
+    assert!((stats.median - 10_000.0).abs() < 1e-9);
+    assert!((stats.std_dev - 48_800_000_f64.sqrt()).abs() < 1e-9);
+    assert_eq!((stats.min_item, stats.max_item), (Some(1000), Some(10_000)));
+    assert_eq!(stats.items_per_elf.into_iter().collect::<Vec<_>>(), [(1, 2), (2, 1), (3, 2)]);
+  }
+
+  #[test]
+  fn percentiles() {
+    let stats = example();
+    assert_eq!([0, 20, 21, 50, 80, 81, 100, 255].map(|percent| stats.percentile(percent)), [4000, 4000, 6000, 10_000, 11_000, 24_000, 24_000, 24_000]);
+  }
+
+  #[test]
+  fn histogram_of_example() {
+    assert_eq!(example().histogram(4, 4), " 4000-9000  #### 2\n 9001-14001 #### 2\n14002-19002  0\n19003-24000 ## 1\n");
+  }
+
+  #[test]
+  fn histogram_of_narrow_range() {
+    let bags = Day01::parse("4000\n\n4015\n\n4007\n".as_bytes()).unwrap();
+    let histogram = CalorieStats::of(&bags).unwrap().histogram(10, 2);
+    assert_eq!(histogram, "4000-4001 ## 1\n4002-4003  0\n4004-4005  0\n4006-4007 ## 1\n4008-4009  0\n4010-4011  0\n4012-4013  0\n4014-4015 ## 1\n");
+  }
+
+  #[test]
+  fn totals_beyond_u32() {
+    let bags = Day01::parse("18446744073709551615\n\n4000000000\n4000000000\n\n0\n".as_bytes()).unwrap();
+    let stats = CalorieStats::of(&bags).unwrap();
+    assert_eq!((stats.min_total(), stats.percentile(50), stats.max_total()), (0, 8_000_000_000, u64::MAX));
+    assert!((stats.median - 8e9).abs() < 1e-3);
+    assert!((stats.mean - (float(u64::MAX.into()) + 8e9) / 3.0).abs() < 1e4);
+    assert_eq!(stats.histogram(2, 4), "                   0-9223372036854775807  #### 2\n 9223372036854775808-18446744073709551615 ## 1\n");
+  }
+
+  #[test]
+  fn no_elves() {
+    assert!(CalorieStats::of(&[]).is_none());
+    let stats = CalorieStats::of(&[ElfFoodBag::new(0)]).unwrap();
+    assert_eq!((stats.min_item, stats.percentile(50), stats.histogram(3, 10).as_str()), (None, 0, "0-0 ########## 1\n"));
+  }
+}
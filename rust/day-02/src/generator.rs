@@ -0,0 +1,19 @@
+use std::fmt::Write;
+
+use aoc_runner::Rng;
+
+/// A strategy guide roughly `scale` times the size of a real puzzle input.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  strategy_guide(&mut Rng::new(seed), 2500 * scale)
+}
+
+/// A strategy guide of `rounds` rounds.
+pub fn strategy_guide(rng: &mut Rng, rounds: usize) -> String {
+  let mut input = String::new();
+  for _ in 0..rounds {
+    writeln!(input, "{} {}", rng.pick(&['A', 'B', 'C']), rng.pick(&['X', 'Y', 'Z'])).unwrap();
+  }
+
+  input
+}
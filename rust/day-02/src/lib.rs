@@ -1,12 +1,15 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path,
 };
+use aoc_runner::{parsing, DayError, Solution};
 
-#[derive(Debug, PartialEq)]
-enum Choice {
+pub mod generator;
+
+/// A shape one of the players makes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Choice {
   Rock,
   Paper,
   Scissors
@@ -23,15 +26,14 @@ enum Strategy {
 pub struct Game((Choice, Choice));
 
 impl Game {
+  #[must_use]
   pub fn get_score(&self) -> u32 {
     let mut score: u32;
     let is_draw = self.0.0 == self.0.1;
-    let is_loss = match self.0 {
-      (Choice::Rock, Choice::Scissors) => true,
-      (Choice::Paper, Choice::Rock) => true,
-      (Choice::Scissors, Choice::Paper) => true,
-      _ => false,
-    };
+    let is_loss = matches!(
+      self.0,
+      (Choice::Rock, Choice::Scissors) | (Choice::Paper, Choice::Rock) | (Choice::Scissors, Choice::Paper)
+    );
 
     if is_draw {
       score = 3;
@@ -42,72 +44,47 @@ impl Game {
     }
 
     match self.0.1 {
-      Choice::Rock => score = score + 1,
-      Choice::Paper => score = score + 2,
-      Choice::Scissors => score = score + 3,
+      Choice::Rock => score += 1,
+      Choice::Paper => score += 2,
+      Choice::Scissors => score += 3,
     }
 
     score
   }
 }
 
-fn parse(filename: impl AsRef<Path>) -> Vec<(String, String)> {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
+// the second column is read as a choice too, which part two takes for a strategy
+fn parse(reader: impl BufRead) -> Result<Vec<(Choice, Choice)>, DayError> {
   let mut parsed = Vec::new();
-  for line in reader.lines() {
-    let line = line.expect("could not parse line");
-    let mut iter = line.split_whitespace();
-    let first = iter.next().expect("could not parse first choice").to_string();
-    let second = iter.next().expect("could not parse second choice").to_string();
+  for line in parsing::lines(reader) {
+    let line = line?;
+    let [first, second] = line.span().fields("expected `<choice> <choice>`")?;
+    let first = first.one_of(&[("A", Choice::Rock), ("B", Choice::Paper), ("C", Choice::Scissors)], "invalid choice")?;
+    let second = second.one_of(&[("X", Choice::Rock), ("Y", Choice::Paper), ("Z", Choice::Scissors)], "invalid choice")?;
     parsed.push((first, second));
   }
 
-  parsed
+  Ok(parsed)
 }
 
-pub fn solve_first(filename: impl AsRef<Path>) -> u32 {
-  let parsed_games = parse(filename);
+fn score_first(parsed_games: &[(Choice, Choice)]) -> u32 {
   let mut games = Vec::new();
 
-  for parsed_game in parsed_games {
-    let (first, second) = parsed_game;
-    let first_choice = match first.as_str() {
-      "A" => Choice::Rock,
-      "B" => Choice::Paper,
-      "C" => Choice::Scissors,
-      _ => panic!("invalid choice"),
-    };
-    let second_choice = match second.as_str() {
-      "X" => Choice::Rock,
-      "Y" => Choice::Paper,
-      "Z" => Choice::Scissors,
-      _ => panic!("invalid choice"),
-    };
-
-    games.push(Game((first_choice, second_choice)));
+  for &(first, second) in parsed_games {
+    games.push(Game((first, second)));
   }
 
-  games.iter().map(|game| game.get_score()).sum()
+  games.iter().map(Game::get_score).sum()
 }
 
-pub fn solve_second(filename: impl AsRef<Path>) -> u32 {
-  let parsed_games = parse(filename);
+fn score_second(parsed_games: &[(Choice, Choice)]) -> u32 {
   let mut games = Vec::new();
 
-  for parsed_game in parsed_games {
-    let (first, second) = parsed_game;
-    let choice = match first.as_str() {
-      "A" => Choice::Rock,
-      "B" => Choice::Paper,
-      "C" => Choice::Scissors,
-      _ => panic!("invalid choice"),
-    };
-    let strategy = match second.as_str() {
-      "X" => Strategy::Lose,
-      "Y" => Strategy::Draw,
-      "Z" => Strategy::Win,
-      _ => panic!("invalid strategy"),
+  for &(choice, second) in parsed_games {
+    let strategy = match second {
+      Choice::Rock => Strategy::Lose,
+      Choice::Paper => Strategy::Draw,
+      Choice::Scissors => Strategy::Win,
     };
 
     let game = match (choice, strategy) {
@@ -125,11 +102,63 @@ pub fn solve_second(filename: impl AsRef<Path>) -> u32 {
     games.push(game);
   }
 
-  games.iter().map(|game| game.get_score()).sum()
+  games.iter().map(Game::get_score).sum()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+  const DAY: u8 = 2;
+
+  type Input = Vec<(Choice, Choice)>;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
+  }
+
+  fn part_one(parsed_games: &Self::Input) -> Result<u32, DayError> {
+    Ok(score_first(parsed_games))
+  }
+
+  fn part_two(parsed_games: &Self::Input) -> Result<u32, DayError> {
+    Ok(score_second(parsed_games))
+  }
+}
+
+/// # Errors
+///
+/// Returns an error if the file cannot be read or has a malformed game.
+pub fn solve_first(filename: impl AsRef<Path>) -> Result<u32, DayError> {
+  Ok(aoc_runner::solve::<Day02>(filename.as_ref())?.0)
+}
+
+/// # Errors
+///
+/// Returns an error if the file cannot be read or has a malformed game.
+pub fn solve_second(filename: impl AsRef<Path>) -> Result<u32, DayError> {
+  Ok(aoc_runner::solve::<Day02>(filename.as_ref())?.1)
+}
+
+/// # Errors
+///
+/// Returns an error if the input has a malformed game.
+pub fn solve_str(input: &str) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_str::<Day02>(input)
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be read or has a malformed game.
+pub fn solve_reader(reader: impl BufRead) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_reader::<Day02>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
@@ -152,25 +181,52 @@ mod tests {
 
   #[test]
   fn solve_first_example() {
-    let result = solve_first("example.txt");
+    let result = solve_first("example.txt").unwrap();
     assert_eq!(result, 15);
   }
 
   #[test]
-  fn solve_first_input() {
-    let result = solve_first("input.txt");
-    assert_eq!(result, 13924);
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day02>();
   }
 
   #[test]
   fn solve_second_example() {
-    let result = solve_second("example.txt");
+    let result = solve_second("example.txt").unwrap();
     assert_eq!(result, 12);
   }
 
   #[test]
-  fn solve_second_input() {
-    let result = solve_second("input.txt");
-    assert_eq!(result, 13448);
+  fn solve_first_malformed() {
+    let error = solve_first("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:2:3: invalid choice: `Q`");
+  }
+
+  #[test]
+  fn parse_malformed() {
+    let error = solve_str("A Y\nA Y Z\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 2, column 1: expected `<choice> <choice>`: `A Y Z`");
+    let error = solve_str("A\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 1, column 1: expected `<choice> <choice>`: `A`");
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str("A Y\nB X\nC Z\n").unwrap(), (15, 12));
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip(games in prop::collection::vec((0..3_usize, 0..3_usize), 0..50)) {
+      let choices = [Choice::Rock, Choice::Paper, Choice::Scissors];
+      let input = games.iter().flat_map(|&(first, second)| [["A", "B", "C"][first], " ", ["X", "Y", "Z"][second], "\n"]).collect::<String>();
+      let expected = games.iter().map(|&(first, second)| (choices[first], choices[second])).collect::<Vec<_>>();
+      prop_assert_eq!(Day02::parse(input.as_bytes())?, expected);
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day02::parse(input.as_bytes());
+    }
+  }
+}
@@ -0,0 +1,9 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_03::{generator, Day03};
+
+fn bench(c: &mut Criterion) {
+  aoc_bench::bench_day::<Day03>(c, generator::generate, aoc_bench::DEFAULT_SCALES);
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
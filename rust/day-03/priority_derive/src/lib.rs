@@ -2,7 +2,6 @@ extern crate proc_macro;
 
 use crate::proc_macro::TokenStream;
 use quote::quote;
-use syn;
 
 #[proc_macro_derive(Priority)]
 pub fn priority_derive(input: TokenStream) -> TokenStream {
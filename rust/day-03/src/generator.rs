@@ -0,0 +1,38 @@
+use aoc_runner::Rng;
+
+/// Rucksacks roughly `scale` times the size of a real puzzle input.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  rucksacks(&mut Rng::new(seed), 100 * scale)
+}
+
+/// `groups` groups of three rucksacks. Every group shares exactly one badge and
+/// every rucksack has exactly one item in both compartments.
+pub fn rucksacks(rng: &mut Rng, groups: usize) -> String {
+  let mut input = String::new();
+  for _ in 0..groups {
+    let mut letters = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+    let (badge, letters) = (letters[51], &letters[..51]);
+
+    // each elf of the group draws from its own 17 letters, so only the badge is shared
+    for pool in letters.chunks(17) {
+      let duplicate = pool[0];
+      let (first_pool, second_pool) = pool[1..].split_at(8);
+      let size = rng.range(4..=24);
+
+      let mut first = vec![duplicate, badge];
+      first.extend((2..size).map(|_| *rng.pick(first_pool)));
+      let mut second = vec![duplicate];
+      second.extend((1..size).map(|_| *rng.pick(second_pool)));
+      rng.shuffle(&mut first);
+      rng.shuffle(&mut second);
+
+      input.extend(first);
+      input.extend(second);
+      input.push('\n');
+    }
+  }
+
+  input
+}
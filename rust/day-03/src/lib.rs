@@ -1,13 +1,18 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path,
   str::FromStr, string::ParseError,
 };
+use aoc_runner::{
+  parsing::{self, Span},
+  DayError, Solution,
+};
 use priority::Priority;
 use priority_derive::Priority;
 
+pub mod generator;
+
 #[derive(Debug, PartialEq, Priority, Clone)]
 pub struct Item(String);
 impl FromStr for Item {
@@ -38,20 +43,24 @@ impl Rucksack {
     duplicates
   }
 
+  #[must_use]
   pub fn get_priority_of_duplicate_items(&self) -> u32 {
-    self.get_duplicate_items().into_iter().map(|item| item.get_priority_of_item()).sum()
+    self.get_duplicate_items().into_iter().map(priority::Priority::get_priority_of_item).sum()
   }
 
+  #[must_use]
   pub fn get_all_items(&self) -> Vec<Item> {
     let mut items = self.0.clone();
     items.extend(self.1.clone());
     items
   }
 }
-impl FromStr for Rucksack {
-  type Err = ParseError;
+impl Rucksack {
+  fn from_span(line: Span) -> Result<Self, DayError> {
+    if let Some((_, item)) = line.chars().find(|(c, _)| !c.is_ascii_alphabetic()) {
+      return Err(item.error("invalid item"));
+    }
 
-  fn from_str(s: &str) -> Result<Self, Self::Err> {
     let str_to_compartment = |s: &str| -> Vec<Item> {
       s.split("")
         .filter(|s| !s.is_empty())
@@ -59,14 +68,25 @@ impl FromStr for Rucksack {
         .collect()
     };
 
+    let s = line.as_str();
     let split_in_half = s.split_at(s.len() / 2);
     Ok(Rucksack(str_to_compartment(split_in_half.0), str_to_compartment(split_in_half.1)))
   }
 }
 
+impl FromStr for Rucksack {
+  type Err = DayError;
+
+  // a string on its own is the first line of its input
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Rucksack::from_span(Span::new(s, 1))
+  }
+}
+
 #[derive(Debug)]
 pub struct ElfGroup(Rucksack, Rucksack, Rucksack);
 impl ElfGroup {
+  #[must_use]
   pub fn find_badge(&self) -> Option<Item> {
     // TODO: can rucksack implement iterator?
     for item in &self.0.get_all_items() {
@@ -80,41 +100,112 @@ impl ElfGroup {
 }
 
 
-fn parse(filename: impl AsRef<Path>) -> Vec<Rucksack> {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
+fn parse(reader: impl BufRead) -> Result<Vec<Rucksack>, DayError> {
   let mut rucksacks = Vec::new();
-  for line in reader.lines() {
-    let line = line.expect("could not parse line");
-    let rucksack = Rucksack::from_str(&line).expect("could not parse rucksack");
-    rucksacks.push(rucksack);
+  for line in parsing::lines(reader) {
+    rucksacks.push(Rucksack::from_span(line?.span())?);
   }
 
-  rucksacks
+  Ok(rucksacks)
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> (u32, u32) {
-  let rucksacks = parse(filename);
+pub struct Day03;
+
+impl Solution for Day03 {
+  const DAY: u8 = 3;
+
+  type Input = Vec<Rucksack>;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
+  }
+
+  fn part_one(rucksacks: &Self::Input) -> Result<u32, DayError> {
+    Ok(rucksacks.iter().map(Rucksack::get_priority_of_duplicate_items).sum())
+  }
+
+  fn part_two(rucksacks: &Self::Input) -> Result<u32, DayError> {
+    if rucksacks.len() % 3 != 0 {
+      return Err(DayError::unsolvable(format!("{} rucksacks cannot be split into groups of three", rucksacks.len())));
+    }
+
+    let groups = rucksacks.chunks(3).map(|r| ElfGroup(r[0].clone(), r[1].clone(), r[2].clone())).collect::<Vec<_>>();
+    groups.into_iter().enumerate().map(|(i, g)| {
+      g.find_badge().map(|badge| badge.get_priority_of_item()).ok_or_else(|| DayError::unsolvable(format!("group {} has no badge", i + 1)))
+    }).sum()
+  }
+}
 
-  let first = rucksacks.clone().into_iter().map(|r| r.get_priority_of_duplicate_items()).sum();
+/// # Errors
+///
+/// Returns an error if the file cannot be read, has an invalid item, or a group has no badge.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve::<Day03>(filename.as_ref())
+}
 
-  let groups = rucksacks.clone().chunks(3).map(|r| ElfGroup(r[0].clone(), r[1].clone(), r[2].clone())).collect::<Vec<_>>();
-  let second: u32 = groups.into_iter().map(|g| g.find_badge().expect("could not find badge").get_priority_of_item()).sum();
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str(input: &str) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_str::<Day03>(input)
+}
 
-  (first, second)
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader(reader: impl BufRead) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_reader::<Day03>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
   fn solve_example() {
-    assert_eq!(solve("example.txt"), (157, 70));
+    assert_eq!(solve("example.txt").unwrap(), (157, 70));
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), (7917, 2585));
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day03>();
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn solve_malformed() {
+    let error = solve("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:3:7: invalid item: `4`");
+  }
+
+  #[test]
+  fn from_str_malformed() {
+    assert_eq!("ab4d".parse::<Rucksack>().unwrap_err().to_string(), "line 1, column 3: invalid item: `4`");
+  }
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str(include_str!("../example.txt")).unwrap(), (157, 70));
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip(rucksacks in prop::collection::vec("([a-zA-Z][a-zA-Z]){1,20}", 0..30)) {
+      let input = rucksacks.iter().flat_map(|rucksack| [rucksack, "\n"]).collect::<String>();
+      let items = |compartment: &str| compartment.chars().map(|item| Item(item.to_string())).collect::<Vec<_>>();
+      let expected = rucksacks.iter().map(|rucksack| {
+        let (first, second) = rucksack.split_at(rucksack.len() / 2);
+        Rucksack(items(first), items(second))
+      }).collect::<Vec<_>>();
+      prop_assert_eq!(Day03::parse(input.as_bytes())?, expected);
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day03::parse(input.as_bytes());
+    }
+  }
+}
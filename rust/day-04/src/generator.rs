@@ -0,0 +1,24 @@
+use std::fmt::Write;
+
+use aoc_runner::Rng;
+
+/// Section assignment pairs roughly `scale` times the size of a real puzzle input.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  section_pairs(&mut Rng::new(seed), 1000 * scale)
+}
+
+/// `pairs` pairs of section assignments between 1 and 99.
+pub fn section_pairs(rng: &mut Rng, pairs: usize) -> String {
+  let mut input = String::new();
+  for _ in 0..pairs {
+    let mut range = || {
+      let start = rng.range(1..=99);
+      (start, rng.range(start..=99))
+    };
+    let (first, second) = (range(), range());
+    writeln!(input, "{}-{},{}-{}", first.0, first.1, second.0, second.1).unwrap();
+  }
+
+  input
+}
@@ -1,90 +1,181 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path,
   str::FromStr,
-  string::ParseError,
-  collections::HashSet
 };
+use aoc_runner::{
+  parsing::{self, Span},
+  DayError, Solution,
+};
+
+pub mod generator;
 
 
+/// The room ranges of two elves, each as its first and last room. A range
+/// whose start is past its end has no rooms.
 #[derive(Debug, PartialEq, Clone)]
-pub struct ElfPair(HashSet<u32>, HashSet<u32>);
+pub struct ElfPair((u32, u32), (u32, u32));
+
+// whether every room of `inner` is in `outer`
+fn contains(outer: (u32, u32), inner: (u32, u32)) -> bool {
+  inner.0 > inner.1 || (outer.0 <= inner.0 && inner.1 <= outer.1)
+}
+
 impl ElfPair {
+  #[must_use]
   pub fn has_full_overlap(&self) -> bool {
-    if self.0.is_subset(&self.1) || self.1.is_subset(&self.0) {
-      return true;
-    }
-
-    false
+    contains(self.0, self.1) || contains(self.1, self.0)
   }
 
+  #[must_use]
   pub fn has_partial_overlap(&self) -> bool {
-    if self.0.intersection(&self.1).count() > 0 {
-      return true;
-    }
+    let ElfPair(first, second) = self;
+    first.0.max(second.0) <= first.1.min(second.1)
+  }
+}
+impl ElfPair {
+  fn from_span(line: Span) -> Result<Self, DayError> {
+    let room_range = |range: Span| -> Result<(u32, u32), DayError> {
+      let (start, end) = range.split_once("-", "expected `<start>-<end>`")?;
+      let start = start.parse::<u32>("invalid room number")?;
+      let end = end.parse::<u32>("invalid room number")?;
+
+      Ok((start, end))
+    };
 
-    false
+    let (first, second) = line.split_once(",", "no room range for second elf")?;
+    Ok(ElfPair(room_range(first)?, room_range(second)?))
   }
 }
+
 impl FromStr for ElfPair {
-  type Err = ParseError;
+  type Err = DayError;
 
+  // a string on its own is the first line of its input
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let str_to_room_range = |s: &str| -> HashSet<u32> {
-      let mut rooms = s.split("-")
-        .filter(|s| !s.is_empty())
-        .map(|s| s.parse::<u32>().expect("Failed to parse room range"));
-      let start = rooms.next().expect("No start room");
-      let end = rooms.next().expect("No end room");
-      let mut rooms = HashSet::new();
-
-      for room in start..=end {
-        rooms.insert(room);
-      }
-
-      rooms
-    };
-
-    let mut split_into_pairs = s.split(",");
-    Ok(ElfPair(str_to_room_range(split_into_pairs.next().expect("no room range for first elf")), str_to_room_range(split_into_pairs.next().expect("no room range for second elf"))))
+    ElfPair::from_span(Span::new(s, 1))
   }
 }
 
 
-fn parse(filename: impl AsRef<Path>) -> Vec<ElfPair> {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
+fn parse(reader: impl BufRead) -> Result<Vec<ElfPair>, DayError> {
   let mut pairs = Vec::new();
-  for line in reader.lines() {
-    let line = line.expect("could not parse line");
-    let pair = ElfPair::from_str(&line).expect("could not parse rucksack");
-    pairs.push(pair);
+  for line in parsing::lines(reader) {
+    pairs.push(ElfPair::from_span(line?.span())?);
   }
 
-  pairs
+  Ok(pairs)
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> (usize, usize) {
-  let pairs = parse(filename);
-  let number_of_fully_overlapping_pairs = pairs.iter().filter(|pair| pair.has_full_overlap()).count();
-  let number_of_partially_overlapping_pairs = pairs.iter().filter(|pair| pair.has_partial_overlap()).count();
+pub struct Day04;
+
+impl Solution for Day04 {
+  const DAY: u8 = 4;
+
+  type Input = Vec<ElfPair>;
+  type PartOne = usize;
+  type PartTwo = usize;
+
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
+  }
 
-  (number_of_fully_overlapping_pairs, number_of_partially_overlapping_pairs)
+  fn part_one(pairs: &Self::Input) -> Result<usize, DayError> {
+    Ok(pairs.iter().filter(|pair| pair.has_full_overlap()).count())
+  }
+
+  fn part_two(pairs: &Self::Input) -> Result<usize, DayError> {
+    Ok(pairs.iter().filter(|pair| pair.has_partial_overlap()).count())
+  }
+}
+
+/// # Errors
+///
+/// Returns an error if the file cannot be read or has a malformed pair of room ranges.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(usize, usize), DayError> {
+  aoc_runner::solve::<Day04>(filename.as_ref())
+}
+
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str(input: &str) -> Result<(usize, usize), DayError> {
+  aoc_runner::solve_str::<Day04>(input)
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader(reader: impl BufRead) -> Result<(usize, usize), DayError> {
+  aoc_runner::solve_reader::<Day04>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use std::collections::HashSet;
+
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
   fn solve_example() {
-    assert_eq!(solve("example.txt"), (2, 4));
+    assert_eq!(solve("example.txt").unwrap(), (2, 4));
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), (550, 931));
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day04>();
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn solve_malformed() {
+    let error = solve("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:2:7: invalid room number: `x`");
+  }
+
+  #[test]
+  fn from_str_malformed() {
+    assert_eq!("2-4,6-x".parse::<ElfPair>().unwrap_err().to_string(), "line 1, column 7: invalid room number: `x`");
+  }
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str("2-4,6-8\n2-8,3-7\n6-6,4-6\n").unwrap(), (2, 2));
+  }
+
+  #[test]
+  fn whole_room_numbers() {
+    assert_eq!(solve_str("0-4294967295,1-2\n4294967295-4294967295,0-4294967294\n").unwrap(), (1, 1));
+  }
+
+  proptest! {
+    // each range as its start and length, so it is never backwards
+    #[test]
+    fn parse_round_trip(pairs in prop::collection::vec(((0..100_u32, 0..20_u32), (0..100_u32, 0..20_u32)), 0..30)) {
+      let input = pairs.iter().map(|((first, first_length), (second, second_length))| {
+        format!("{first}-{},{second}-{}", first + first_length, second + second_length)
+      }).collect::<Vec<_>>().join("\n");
+      let expected = pairs.iter().map(|((first, first_length), (second, second_length))| {
+        ElfPair((*first, first + first_length), (*second, second + second_length))
+      }).collect::<Vec<_>>();
+      prop_assert_eq!(Day04::parse(input.as_bytes())?, expected);
+    }
+
+    // against the rooms themselves, with ranges that may be backwards
+    #[test]
+    fn overlaps_near_u32_max(ranges in prop::array::uniform4(u32::MAX - 40..=u32::MAX)) {
+      let [first_start, first_end, second_start, second_end] = ranges;
+      let pair = format!("{first_start}-{first_end},{second_start}-{second_end}").parse::<ElfPair>()?;
+      let (first, second) = ((first_start..=first_end).collect::<HashSet<_>>(), (second_start..=second_end).collect::<HashSet<_>>());
+      prop_assert_eq!(pair.has_full_overlap(), first.is_subset(&second) || second.is_subset(&first));
+      prop_assert_eq!(pair.has_partial_overlap(), !first.is_disjoint(&second));
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day04::parse(input.as_bytes());
+    }
+  }
+}
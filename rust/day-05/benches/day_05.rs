@@ -0,0 +1,9 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_05::{generator, Day05};
+
+fn bench(c: &mut Criterion) {
+  aoc_bench::bench_day::<Day05>(c, generator::generate, aoc_bench::DEFAULT_SCALES);
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
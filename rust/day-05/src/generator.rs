@@ -0,0 +1,55 @@
+use std::fmt::Write;
+
+use aoc_runner::Rng;
+
+const STACKS: usize = 9;
+
+/// A crate diagram and move list roughly `scale` times the size of a real puzzle
+/// input. Stacks grow with the scale too.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  crate_plan(&mut Rng::new(seed), 8 * scale, 500 * scale)
+}
+
+/// Nine stacks of up to `max_height` crates and `moves` moves, each of which
+/// only takes crates that are actually there.
+///
+/// # Panics
+///
+/// Panics if `max_height` is zero.
+pub fn crate_plan(rng: &mut Rng, max_height: usize, moves: usize) -> String {
+  let mut heights = (0..STACKS).map(|_| rng.range(1..=max_height)).collect::<Vec<_>>();
+  let tallest = heights.iter().copied().max().unwrap_or_default();
+  let letters = ('A'..='Z').collect::<Vec<_>>();
+
+  let mut input = String::new();
+  for level in (0..tallest).rev() {
+    let row = heights.iter().map(|height| {
+      if *height > level {
+        format!("[{}]", rng.pick(&letters))
+      } else {
+        String::from("   ")
+      }
+    }).collect::<Vec<_>>();
+    writeln!(input, "{}", row.join(" ")).unwrap();
+  }
+  let numbers = (1..=STACKS).map(|n| format!(" {n} ")).collect::<Vec<_>>();
+  writeln!(input, "{}", numbers.join(" ")).unwrap();
+  input.push('\n');
+
+  for _ in 0..moves {
+    let from = loop {
+      let stack = rng.below(STACKS);
+      if heights[stack] > 0 {
+        break stack;
+      }
+    };
+    let to = (from + rng.range(1..=STACKS - 1)) % STACKS;
+    let count = rng.range(1..=heights[from].min(10));
+    heights[from] -= count;
+    heights[to] += count;
+    writeln!(input, "move {count} from {} to {}", from + 1, to + 1).unwrap();
+  }
+
+  input
+}
@@ -1,13 +1,26 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path,
 };
+use aoc_runner::{
+  parsing::{self, Span},
+  DayError, Solution,
+};
+use tracing::debug;
+
+pub mod generator;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct CrateStack(Vec<String>);
+impl Default for CrateStack {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl CrateStack {
+  #[must_use]
   pub fn new() -> Self {
     Self(vec![])
   }
@@ -25,11 +38,17 @@ impl CrateStack {
     }
   }
 
+  /// The crates from the top of the stack down.
+  #[must_use]
+  pub fn crates(&self) -> &[String] {
+    &self.0
+  }
+
   pub fn shift_crate(&mut self) -> Option<String> {
-    if self.0.len() >= 1 {
-      return Some(self.0.remove(0));
-    } else {
+    if self.0.is_empty() {
       None
+    } else {
+      Some(self.0.remove(0))
     }
   }
 
@@ -57,120 +76,299 @@ pub struct Instruction {
   ending_stack: usize,
 }
 
+impl Instruction {
+  fn trace(&self, crane: u32) {
+    debug!(crane, count = self.number_of_crates_to_move, from = self.starting_stack + 1, to = self.ending_stack + 1, "move");
+  }
+
+  // no crane can take more crates than its starting stack has
+  fn check(&self, stacks: &[CrateStack]) -> Result<(), DayError> {
+    let height = stacks[self.starting_stack].crates().len();
+    if self.number_of_crates_to_move > height {
+      return Err(DayError::unsolvable(format!(
+        "cannot move {} crates from stack {}, which has {height}",
+        self.number_of_crates_to_move,
+        self.starting_stack + 1,
+      )));
+    }
+
+    Ok(())
+  }
+}
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct CratePlan(Vec<CrateStack>, Vec<Instruction>);
 impl CratePlan {
-  pub fn run_9000(&mut self) -> Self {
+  /// # Errors
+  ///
+  /// Returns an error if a move takes more crates than its stack has.
+  pub fn run_9000(&mut self) -> Result<Self, DayError> {
     for instruction in &self.1 {
+      instruction.trace(9000);
+      instruction.check(&self.0)?;
       for _ in 0..instruction.number_of_crates_to_move {
         let crate_name = self.0[instruction.starting_stack].shift_crate();
         if let Some(crate_name) = crate_name {
-          self.0[instruction.ending_stack].add_crate_to_top(crate_name.to_string());
+          self.0[instruction.ending_stack].add_crate_to_top(crate_name);
         }
         
       }
     }
 
-    self.clone()
+    Ok(self.clone())
   }
 
-  pub fn run_9001(&mut self) -> Self {
+  /// # Errors
+  ///
+  /// Returns an error if a move takes more crates than its stack has.
+  pub fn run_9001(&mut self) -> Result<Self, DayError> {
     for instruction in &self.1 {
+      instruction.trace(9001);
+      instruction.check(&self.0)?;
       let crate_names = self.0[instruction.starting_stack].shift_crates(instruction.number_of_crates_to_move);
       self.0[instruction.ending_stack].add_crates_to_top(crate_names);
     }
 
-    self.clone()
+    Ok(self.clone())
+  }
+
+  #[must_use]
+  pub fn stacks(&self) -> &[CrateStack] {
+    &self.0
+  }
+
+  /// The stacks side by side with their numbers underneath, the way the puzzle
+  /// draws them.
+  #[must_use]
+  pub fn draw(&self) -> Vec<String> {
+    let height = self.0.iter().map(|stack| stack.crates().len()).max().unwrap_or_default();
+    let mut lines = (0..height).map(|level| {
+      let row = self.0.iter().map(|stack| {
+        let crates = stack.crates();
+        // crates are listed from the top, which is `height - crates.len()` levels down
+        level.checked_sub(height - crates.len()).and_then(|index| crates.get(index)).map_or(String::from("   "), |name| format!("[{name}]"))
+      });
+      row.collect::<Vec<_>>().join(" ").trim_end().to_string()
+    }).collect::<Vec<_>>();
+    let numbers = (1..=self.0.len()).map(|number| format!(" {number} ")).collect::<Vec<_>>();
+    lines.push(numbers.join(" ").trim_end().to_string());
+
+    lines
+  }
+
+  /// The stacks after each crane has run the plan, drawn like
+  /// [`CratePlan::draw`] under the crane's name and with a blank line after.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if a move takes more crates than its stack has.
+  pub fn draw_both(&self) -> Result<Vec<String>, DayError> {
+    let mut lines = vec![];
+    for (crane, moved) in [("CrateMover 9000", self.clone().run_9000()?), ("CrateMover 9001", self.clone().run_9001()?)] {
+      lines.push(String::from(crane));
+      lines.extend(moved.draw());
+      lines.push(String::new());
+    }
+
+    Ok(lines)
   }
 
+  #[must_use]
   pub fn get_top_crates(&self) -> String {
     self.0.iter().map(|stack| {
       let top_crate = stack.0.first();
       if let Some(top_crate) = top_crate {
-        top_crate.to_string()
+        top_crate.clone()
       } else {
-        "".to_string()
+        String::new()
       }
     }).fold(String::new(), |acc, c| {
-      return acc + &c;
+      acc + &c
     })
   }
 }
 
 
-fn parse(filename: impl AsRef<Path>) -> CratePlan {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
-  let mut have_all_stacks = false;
+fn parse(reader: impl BufRead) -> Result<CratePlan, DayError> {
+  let mut groups = parsing::groups(reader);
   let mut stacks: Vec<CrateStack> = Vec::new();
+  for line in groups.next().transpose()?.unwrap_or_default() {
+    // each `[A] ` column of the drawing is one stack, the last row numbers them
+    for (i, column) in line.span().chunks(4).enumerate() {
+      if stacks.get(i).is_none() {
+        stacks.insert(i, CrateStack::new());
+      }
+
+      if let Some((crate_name, _)) = column.chars().nth(1).filter(|(c, _)| c.is_ascii_uppercase()) {
+        stacks[i].add_crate_to_bottom(crate_name.to_string());
+      }
+    }
+  }
+
   let mut instructions = Vec::new();
-  for line in reader.lines() {
-    let line = line.expect("could not parse line");
+  for group in groups {
+    for line in group? {
+      let span = line.span();
+      let [keyword_move, count, keyword_from, from, keyword_to, to] = span.fields("expected `move <n> from <stack> to <stack>`")?;
+      keyword_move.keyword("move")?;
+      keyword_from.keyword("from")?;
+      keyword_to.keyword("to")?;
 
-    if line.is_empty() {
-      have_all_stacks = true;
-      continue;
+      let stack_index = |word: Span| match word.parse::<usize>("invalid stack")? {
+        n if (1..=stacks.len()).contains(&n) => Ok(n - 1),
+        _ => Err(word.error("no such stack")),
+      };
+      instructions.push(Instruction {
+        number_of_crates_to_move: count.parse("invalid number of crates")?,
+        starting_stack: stack_index(from)?,
+        ending_stack: stack_index(to)?,
+      });
     }
+  }
 
-    if !have_all_stacks {
-      let l = line;
-      let exploded = l.split("");
-      let non_empty_crates = exploded.filter(|l| !l.is_empty()).collect::<Vec<&str>>();
-      let rows = non_empty_crates.chunks(4);
+  Ok(CratePlan(stacks, instructions))
+}
 
-      // for each crate in chunk, add crate to stack matching index
-      for (i, row) in rows.enumerate() {
-        if stacks.get(i).is_none() {
-          stacks.insert(i, CrateStack::new());
-        }
+pub struct Day05;
 
-        match row[1].chars().next().expect("could not get first char") {
-          'A'..='Z' => stacks[i].add_crate_to_bottom(row[1].to_string()),
-          _ => {}
-        }
-      }
-    } else {
-      let l = line;
-      let exploded = l.split(" ");
-      let ns = exploded.fold(vec![], |acc, l| {
-        if let Ok(n) = l.parse::<usize>() {
-          return [acc, vec![n]].concat();
-        }
+impl Solution for Day05 {
+  const DAY: u8 = 5;
 
-        acc
-      });
+  type Input = CratePlan;
+  type PartOne = String;
+  type PartTwo = String;
 
-      instructions.push(Instruction {
-        number_of_crates_to_move: ns[0],
-        starting_stack: ns[1] - 1,
-        ending_stack: ns[2] - 1,
-      });
-    }
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
+  }
+
+  fn part_one(plan: &Self::Input) -> Result<String, DayError> {
+    Ok(plan.clone().run_9000()?.get_top_crates())
   }
 
-  CratePlan(stacks, instructions)
+  fn part_two(plan: &Self::Input) -> Result<String, DayError> {
+    Ok(plan.clone().run_9001()?.get_top_crates())
+  }
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> (String, String) {
-  let plan = parse(filename);
-  let old = plan.clone().run_9000();
-  let new = plan.clone().run_9001();
-  
-  (old.get_top_crates(), new.get_top_crates())
+/// # Errors
+///
+/// Returns an error if the file cannot be read, has a malformed move, or has a
+/// move taking more crates than its stack has.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(String, String), DayError> {
+  aoc_runner::solve::<Day05>(filename.as_ref())
+}
+
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str(input: &str) -> Result<(String, String), DayError> {
+  aoc_runner::solve_str::<Day05>(input)
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader(reader: impl BufRead) -> Result<(String, String), DayError> {
+  aoc_runner::solve_reader::<Day05>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use std::fmt::Write;
+
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
   fn solve_example() {
-    assert_eq!(solve("example.txt"), (String::from("CMZ"), String::from("MCD")));
+    assert_eq!(solve("example.txt").unwrap(), (String::from("CMZ"), String::from("MCD")));
+  }
+
+  #[test]
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day05>();
+  }
+
+  #[test]
+  fn solve_malformed() {
+    let error = solve("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:7:18: no such stack: `4`");
+  }
+
+  #[test]
+  fn stacks_after_moving() {
+    let plan = Day05::parse(aoc_runner::open(Path::new("example.txt")).unwrap()).unwrap();
+    let stacks = plan.clone().run_9000().unwrap().stacks().iter().map(|stack| stack.crates().join("")).collect::<Vec<_>>();
+    assert_eq!(stacks, ["C", "M", "ZNDP"]);
+  }
+
+  #[test]
+  fn move_more_crates_than_the_stack_has() {
+    for count in [2, usize::MAX] {
+      let plan = Day05::parse(format!("[A]\n 1   2\n\nmove {count} from 1 to 2\n").as_bytes()).unwrap();
+      let expected = format!("cannot move {count} crates from stack 1, which has 1");
+      assert_eq!(Day05::part_one(&plan).unwrap_err().to_string(), expected);
+      assert_eq!(Day05::part_two(&plan).unwrap_err().to_string(), expected);
+    }
+    let error = solve_str("    [B]\n[A] [C]\n 1   2\n\nmove 1 from 2 to 1\nmove 3 from 1 to 2\n").unwrap_err();
+    assert_eq!(error.to_string(), "cannot move 3 crates from stack 1, which has 2");
+  }
+
+  #[test]
+  fn draw_example() {
+    let plan = Day05::parse(aoc_runner::open(Path::new("example.txt")).unwrap()).unwrap();
+    assert_eq!(plan.draw(), ["    [D]", "[N] [C]", "[Z] [M] [P]", " 1   2   3"]);
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), (String::from("SVFDLGLWV"), String::from("DCVTCVPCL")));
+  fn solve_str_example() {
+    assert_eq!(solve_str(include_str!("../example.txt")).unwrap(), (String::from("CMZ"), String::from("MCD")));
   }
-}
\ No newline at end of file
+
+  // the number of crates to move and the zero based stacks to move them between
+  type Move = (usize, usize, usize);
+
+  // stacks are listed bottom first
+  fn render_plan(stacks: &[Vec<char>], moves: &[Move]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or_default();
+    let mut input = String::new();
+    for level in (0..height).rev() {
+      let row = stacks.iter().map(|stack| stack.get(level).map_or(String::from("   "), |name| format!("[{name}]")));
+      writeln!(input, "{}", row.collect::<Vec<_>>().join(" ")).unwrap();
+    }
+    let numbers = (1..=stacks.len()).map(|number| format!(" {number} "));
+    writeln!(input, "{}\n", numbers.collect::<Vec<_>>().join(" ")).unwrap();
+    for (count, from, to) in moves {
+      writeln!(input, "move {count} from {} to {}", from + 1, to + 1).unwrap();
+    }
+
+    input
+  }
+
+  fn crate_plan() -> impl Strategy<Value = (Vec<Vec<char>>, Vec<Move>)> {
+    (1..10_usize).prop_flat_map(|count| {
+      (
+        prop::collection::vec(prop::collection::vec(prop::char::range('A', 'Z'), 0..6), count),
+        prop::collection::vec((1..5_usize, 0..count, 0..count), 0..10),
+      )
+    })
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip((stacks, moves) in crate_plan()) {
+      let expected = CratePlan(
+        stacks.iter().map(|stack| CrateStack(stack.iter().rev().map(char::to_string).collect())).collect(),
+        moves.iter().map(|&(number_of_crates_to_move, starting_stack, ending_stack)| Instruction { number_of_crates_to_move, starting_stack, ending_stack }).collect(),
+      );
+      prop_assert_eq!(Day05::parse(render_plan(&stacks, &moves).as_bytes())?, expected);
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day05::parse(input.as_bytes());
+    }
+  }
+}
@@ -0,0 +1,35 @@
+use aoc_runner::Rng;
+
+/// A datastream roughly `scale` times the size of a real puzzle input, with its
+/// markers 40% of the way in.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  let length = 4096 * scale;
+  datastream(&mut Rng::new(seed), length, length * 2 / 5)
+}
+
+/// A datastream of `length` characters whose first start-of-packet and
+/// start-of-message markers both begin right after `marker_at` characters, so
+/// the answers are `marker_at + 4` and `marker_at + 14`.
+///
+/// # Panics
+///
+/// Panics if there is no room for a marker at `marker_at`.
+pub fn datastream(rng: &mut Rng, length: usize, marker_at: usize) -> String {
+  assert!(marker_at + 14 <= length, "no room for a marker at {marker_at}");
+  let mut marker = ('a'..='n').collect::<Vec<_>>();
+  rng.shuffle(&mut marker);
+
+  // two letters and then the first letter of the marker can never make a marker
+  // of their own, nor one that reaches into the marker
+  let mut input = (1..marker_at).map(|_| *rng.pick(&['x', 'y'])).collect::<String>();
+  if marker_at > 0 {
+    input.push(marker[0]);
+  }
+  input.extend(marker);
+  let letters = ('a'..='z').collect::<Vec<_>>();
+  input.extend((input.len()..length).map(|_| *rng.pick(&letters)));
+  input.push('\n');
+
+  input
+}
@@ -1,74 +1,164 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path,
 };
+use aoc_runner::{parsing, DayError, Solution};
 
+pub mod generator;
 
-fn parse(filename: impl AsRef<Path>) -> Vec<char> {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
-  reader.lines().next().expect("no lines").expect("could not read line").chars().collect()
+
+fn parse(reader: impl BufRead) -> Result<Vec<char>, DayError> {
+  let line = parsing::lines(reader).next().ok_or_else(|| DayError::unsolvable("no datastream"))??;
+
+  Ok(line.text().chars().collect())
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> (u32, u32) {
-  let message = parse(filename);
-
-  let get_position = |packet_size: u8| {
-    let mut position = packet_size as u32;
-    for window in message.windows(packet_size as usize) {
-      let mut uniques: Vec<&char> = Vec::new();
-      for c in window {
-        if !uniques.contains(&c) {
-          uniques.push(c);
-        }
+// the number of characters up to the end of the first `packet_size` different
+// characters in a row, or `None` if there are none
+fn get_position(message: &[char], packet_size: u8) -> Option<usize> {
+  let start = message.windows(packet_size as usize).position(|window| {
+    let mut uniques: Vec<&char> = Vec::new();
+    for c in window {
+      if !uniques.contains(&c) {
+        uniques.push(c);
       }
+    }
 
-      if uniques.len() == window.len() {
-        break
-      }
+    uniques.len() == window.len()
+  })?;
 
-      position += 1;
-    }
+  Some(start + packet_size as usize)
+}
 
-    position
-  };
+fn marker(message: &[char], packet_size: u8, name: &str) -> Result<u32, DayError> {
+  let position = get_position(message, packet_size).ok_or_else(|| DayError::unsolvable(format!("no {name} marker")))?;
+  u32::try_from(position).map_err(|_| DayError::unsolvable(format!("the {name} marker is past character {}", u32::MAX)))
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+  const DAY: u8 = 6;
+
+  type Input = Vec<char>;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
+  }
 
-  (get_position(4), get_position(14))
+  fn part_one(message: &Self::Input) -> Result<u32, DayError> {
+    marker(message, 4, "start-of-packet")
+  }
+
+  fn part_two(message: &Self::Input) -> Result<u32, DayError> {
+    marker(message, 14, "start-of-message")
+  }
+}
+
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is empty, or has no marker.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve::<Day06>(filename.as_ref())
+}
+
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str(input: &str) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_str::<Day06>(input)
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader(reader: impl BufRead) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_reader::<Day06>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
   fn solve_example_1() {
-    assert_eq!(solve("example_1.txt"), (7, 19));
+    assert_eq!(solve("example_1.txt").unwrap(), (7, 19));
   }
 
   #[test]
   fn solve_example_2() {
-    assert_eq!(solve("example_2.txt"), (5, 23));
+    assert_eq!(solve("example_2.txt").unwrap(), (5, 23));
   }
 
   #[test]
   fn solve_example_3() {
-    assert_eq!(solve("example_3.txt"), (6, 23));
+    assert_eq!(solve("example_3.txt").unwrap(), (6, 23));
   }
 
   #[test]
   fn solve_example_4() {
-    assert_eq!(solve("example_4.txt"), (10, 29));
+    assert_eq!(solve("example_4.txt").unwrap(), (10, 29));
   }
 
   #[test]
   fn solve_example_5() {
-    assert_eq!(solve("example_5.txt"), (11, 26));
+    assert_eq!(solve("example_5.txt").unwrap(), (11, 26));
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), (1647, 2447));
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day06>();
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn solve_empty() {
+    let error = solve("empty.txt").unwrap_err();
+    assert_eq!(error.to_string(), "no datastream");
+  }
+
+  #[test]
+  fn solve_without_marker() {
+    let error = solve_str("abc\n").unwrap_err();
+    assert_eq!(error.to_string(), "no start-of-packet marker");
+    let error = Day06::part_two(&"abcdefghijklmabcd".chars().collect()).unwrap_err();
+    assert_eq!(error.to_string(), "no start-of-message marker");
+  }
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(), (7, 19));
+  }
+
+  // the markers start 40% of the way into the 4096 characters
+  #[test]
+  fn solve_generated() {
+    for seed in 0..5 {
+      assert_eq!(solve_str(&generator::generate(seed, 1)).unwrap(), (1638 + 4, 1638 + 14));
+    }
+  }
+
+  #[test]
+  fn solve_generated_marker() {
+    for marker_at in [0_u32, 1, 2, 3, 500, 986] {
+      let input = generator::datastream(&mut aoc_runner::Rng::new(6), 1000, marker_at as usize);
+      assert_eq!(solve_str(&input).unwrap(), (marker_at + 4, marker_at + 14));
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip(datastream in "[a-z]{1,100}") {
+      prop_assert_eq!(Day06::parse(format!("{datastream}\n").as_bytes())?, datastream.chars().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day06::parse(input.as_bytes());
+    }
+  }
+}
@@ -1,16 +1,6 @@
-
-
-use std::env;
-use std::time::Instant;
+#[global_allocator]
+static ALLOCATOR: aoc_runner::alloc::CountingAllocator = aoc_runner::alloc::CountingAllocator;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let filename = &args[1];
-
-    let time = Instant::now();
-    println!(
-        "answer: {:?}, time: {:.2?}",
-        day_06::solve(filename),
-        time.elapsed()
-    );
-}
\ No newline at end of file
+  aoc_runner::main::<day_06::Day06>();
+}
@@ -0,0 +1,70 @@
+use std::fmt::Write;
+
+use aoc_runner::Rng;
+
+struct Directory {
+  name: String,
+  files: Vec<(String, usize)>,
+  children: Vec<usize>,
+}
+
+fn name(rng: &mut Rng, id: usize) -> String {
+  let letters = ('a'..='z').collect::<Vec<_>>();
+  let mut name = (0..rng.range(1..=6)).map(|_| *rng.pick(&letters)).collect::<String>();
+  write!(name, "{id}").unwrap();
+  name
+}
+
+/// A terminal transcript roughly `scale` times the size of a real puzzle input.
+/// File sizes shrink as the scale grows so the disk never overflows.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  transcript(&mut Rng::new(seed), 200 * scale, (300_000 / scale.max(1)).max(1))
+}
+
+/// A transcript exploring a random tree of `directories` directories (including
+/// `/`), each holding up to three files of at most `max_file_size`.
+///
+/// # Panics
+///
+/// Panics if `max_file_size` is zero.
+pub fn transcript(rng: &mut Rng, directories: usize, max_file_size: usize) -> String {
+  let mut tree = vec![Directory { name: String::from("/"), files: vec![], children: vec![] }];
+  for id in 1..directories {
+    let parent = rng.below(tree.len());
+    tree[parent].children.push(id);
+    tree.push(Directory { name: name(rng, id), files: vec![], children: vec![] });
+  }
+  for (id, directory) in tree.iter_mut().enumerate() {
+    directory.files = (0..rng.range(0..=3)).map(|n| (name(rng, id * 4 + n), rng.range(1..=max_file_size))).collect();
+  }
+
+  let mut input = String::from("$ cd /\n");
+  // walk the tree depth first, `None` meaning "go back up"
+  let mut stack = vec![Some(0)];
+  while let Some(next) = stack.pop() {
+    let Some(id) = next else {
+      input.push_str("$ cd ..\n");
+      continue;
+    };
+
+    let directory = &tree[id];
+    if id != 0 {
+      writeln!(input, "$ cd {}", directory.name).unwrap();
+    }
+    input.push_str("$ ls\n");
+    for child in &directory.children {
+      writeln!(input, "dir {}", tree[*child].name).unwrap();
+    }
+    for (file, size) in &directory.files {
+      writeln!(input, "{size} {file}").unwrap();
+    }
+
+    for child in directory.children.iter().rev() {
+      stack.push(None);
+      stack.push(Some(*child));
+    }
+  }
+
+  input
+}
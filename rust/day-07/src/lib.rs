@@ -1,9 +1,12 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path, collections::HashMap,
 };
+use aoc_runner::{parsing, DayError, Solution};
+use tracing::debug;
+
+pub mod generator;
 
 #[derive(Debug)]
 struct Entry {
@@ -11,51 +14,56 @@ struct Entry {
   path: String
 }
 
-fn parse(filename: impl AsRef<Path>) -> Vec<Entry> {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
+fn parse(reader: impl BufRead) -> Result<Vec<Entry>, DayError> {
   let mut entries: Vec<Entry> = vec![];
   let mut current_path: Vec<String> = vec![];
-  for line in reader.lines() {
-    let line = line.expect("could not parse line");
+  for line in parsing::lines(reader) {
+    let line = line?;
+    let span = line.span();
 
-    match line.chars().next().expect("empty line") {
+    match line.text().chars().next() {
       // command
-      '$' => {
-        let parts = line.split(' ').map(|s| s.to_string()).collect::<Vec<String>>();
-        if parts[1] == "ls" {
-          // noop
-        } else if parts[1] == "cd" {
-          let path = &parts[2];
-
-          if path == ".." {
-            current_path.pop();
-          } else if path == "/" {
-            current_path.push(String::from("/root"));
-          } else {
-            current_path.push(path.to_string());
+      Some('$') => {
+        let command = span.keyword("$")?.word(0, "missing command")?;
+        match command.as_str() {
+          "ls" => {
+            // noop
+          }
+          "cd" => {
+            let path = span.word(2, "missing directory")?.as_str();
+
+            if path == ".." {
+              current_path.pop();
+            } else if path == "/" {
+              current_path.push(String::from("/root"));
+            } else {
+              current_path.push(path.to_string());
+            }
+            debug!(path = current_path.join("/"), "cd");
           }
+          _ => return Err(command.error("unknown command")),
         }
       }
       // file
-      '0'..='9' => {
-        let parts = line.split(' ').collect::<Vec<&str>>();
+      Some('0'..='9') => {
+        let [size, name] = span.fields("expected `<size> <name>`")?;
         let entry = Entry {
-          size: parts[0].parse().expect("not a number"),
+          size: size.parse("invalid file size")?,
           path: current_path.join("/")
         };
-        entries.push(entry)
+        debug!(size = entry.size, directory = entry.path, name = name.as_str(), "file");
+        entries.push(entry);
       }
+      None => return Err(span.error("empty line")),
       // nothing
       _ => {}
     }
   }
 
-  entries
+  Ok(entries)
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> (u32, u32) {
-  let entries = parse(filename);
+fn get_directory_sizes(entries: &[Entry]) -> Result<HashMap<String, u32>, DayError> {
   let mut directories: HashMap<String, u32> = HashMap::new();
 
   for entry in entries {
@@ -63,30 +71,144 @@ pub fn solve(filename: impl AsRef<Path>) -> (u32, u32) {
     let mut current_path: Vec<&str> = vec![];
     for dir in dirs {
       current_path.push(dir);
-      directories.entry(current_path.join("/")).and_modify(|e| *e += entry.size).or_insert(entry.size);
+      let size = directories.entry(current_path.join("/")).or_default();
+      *size = size.checked_add(entry.size).ok_or_else(|| DayError::unsolvable("directory sizes do not fit in 32 bits"))?;
     }
   }
 
-  let all_small_directories = directories.clone().into_iter().filter(|(_, size)| *size <= 100000).map(|(_, size)| size).sum::<u32>();
+  Ok(directories)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+  const DAY: u8 = 7;
 
-  let unused_space = 70000000 - directories.clone().get_key_value("/root").unwrap().1;
-  let space_needed = 30000000 - unused_space;
-  let smallest_directory_to_delete_size = directories.clone().into_iter().filter(|(_, size)| *size >= space_needed).map(|(_, size)| size).min().expect("no directory small enough");
+  type Input = HashMap<String, u32>;
+  type PartOne = u32;
+  type PartTwo = u32;
 
-  (all_small_directories, smallest_directory_to_delete_size)
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    get_directory_sizes(&parse(reader)?)
+  }
+
+  fn part_one(directories: &Self::Input) -> Result<u32, DayError> {
+    Ok(directories.values().filter(|size| **size <= 100_000).sum::<u32>())
+  }
+
+  fn part_two(directories: &Self::Input) -> Result<u32, DayError> {
+    let used_space = directories.get("/root").ok_or_else(|| DayError::unsolvable("never visited `/`"))?;
+    let unused_space = 70_000_000_u32.checked_sub(*used_space).ok_or_else(|| DayError::unsolvable("more space used than the disk holds"))?;
+    let space_needed = 30_000_000_u32.saturating_sub(unused_space);
+    directories.values().filter(|size| **size >= space_needed).copied().min().ok_or_else(|| DayError::unsolvable("no directory big enough to delete"))
+  }
+}
+
+/// # Errors
+///
+/// Returns an error if the file cannot be read, has a malformed line, or never lists `/`.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve::<Day07>(filename.as_ref())
+}
+
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str(input: &str) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_str::<Day07>(input)
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader(reader: impl BufRead) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_reader::<Day07>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use std::fmt::Write;
+
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
   fn solve_example() {
-    assert_eq!(solve("example.txt"), (95437, 24933642));
+    assert_eq!(solve("example.txt").unwrap(), (95_437, 24_933_642));
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), (1428881, 10475598));
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day07>();
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn solve_malformed() {
+    let error = solve("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:4:1: invalid file size: `14848514b`");
+  }
+
+  #[test]
+  fn solve_too_big() {
+    let error = solve_str("$ cd /\n$ ls\n4000000000 a\n4000000000 b\n").unwrap_err();
+    assert_eq!(error.to_string(), "directory sizes do not fit in 32 bits");
+  }
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str(include_str!("../example.txt")).unwrap(), (95_437, 24_933_642));
+  }
+
+  #[derive(Debug, Clone)]
+  struct Directory {
+    files: Vec<(String, u32)>,
+    directories: Vec<(String, Directory)>,
+  }
+
+  fn directory() -> impl Strategy<Value = Directory> {
+    let files = prop::collection::vec(("[a-z]{1,8}(\\.[a-z]{1,3})?", 1..100_000_u32), 0..4);
+    let leaf = files.clone().prop_map(|files| Directory { files, directories: vec![] });
+    leaf.prop_recursive(3, 20, 3, move |inner| {
+      (files.clone(), prop::collection::vec(("[a-z]{1,8}", inner), 0..3)).prop_map(|(files, directories)| Directory { files, directories })
+    })
+  }
+
+  // lists `directory` and then walks into each directory in it, adding up the
+  // size every directory on the way should end up with
+  fn render_transcript(directory: &Directory, path: &mut Vec<String>, transcript: &mut String, sizes: &mut HashMap<String, u32>) {
+    transcript.push_str("$ ls\n");
+    for (name, _) in &directory.directories {
+      writeln!(transcript, "dir {name}").unwrap();
+    }
+    for (name, size) in &directory.files {
+      writeln!(transcript, "{size} {name}").unwrap();
+      for depth in 1..=path.len() {
+        *sizes.entry(path[..depth].join("/")).or_default() += size;
+      }
+    }
+
+    for (name, inner) in &directory.directories {
+      writeln!(transcript, "$ cd {name}").unwrap();
+      path.push(name.clone());
+      render_transcript(inner, path, transcript, sizes);
+      path.pop();
+      transcript.push_str("$ cd ..\n");
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip(root in directory()) {
+      let (mut transcript, mut sizes) = (String::from("$ cd /\n"), HashMap::new());
+      // `/` is kept as `/root`, and every path starts with the empty name before it
+      render_transcript(&root, &mut vec![String::new(), String::from("root")], &mut transcript, &mut sizes);
+      prop_assert_eq!(Day07::parse(transcript.as_bytes())?, sizes);
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day07::parse(input.as_bytes());
+    }
+  }
+}
@@ -0,0 +1,9 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_08::{generator, Day08};
+
+fn bench(c: &mut Criterion) {
+  aoc_bench::bench_day::<Day08>(c, generator::generate, aoc_bench::DEFAULT_SCALES);
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+use aoc_runner::Rng;
+
+/// A square forest with roughly `scale` times as many trees as a real puzzle input.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  let side = (99 * 99 * scale).isqrt();
+  forest(&mut Rng::new(seed), side, side)
+}
+
+/// A `width` by `height` grid of tree heights.
+pub fn forest(rng: &mut Rng, width: usize, height: usize) -> String {
+  let digits = ('0'..='9').collect::<Vec<_>>();
+
+  let mut input = String::with_capacity(height * (width + 1));
+  for _ in 0..height {
+    input.extend((0..width).map(|_| *rng.pick(&digits)));
+    input.push('\n');
+  }
+
+  input
+}
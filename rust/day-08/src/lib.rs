@@ -1,210 +1,184 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path,
 };
+use aoc_runner::{DayError, Solution};
+use grid::{Grid, Point, Vector};
 
-#[derive(Debug)]
-struct Forest {
-  width: usize,
-  height: usize,
-  pub rows: Vec<Vec<u8>>,
+pub mod generator;
+
+#[derive(Debug, PartialEq)]
+pub struct Forest {
+  pub trees: Grid<u8>,
 }
 impl Forest {
-  pub fn is_tree_visible(&self, x: usize, y: usize) -> bool {
-    if x == 0 || y == 0 || y >= self.rows.len() || x >= self.rows[y].len() {
-      return true;
-    }
-  
-    let tree = self.rows[y][x];
-    let taller_trees_above = &self.rows[0..=y-1].into_iter().map(|row| row[x]).filter(|tree_above| tree_above >= &tree).collect::<Vec<u8>>();
-    let taller_trees_below = &self.rows[y+1..].into_iter().map(|row| row[x]).filter(|tree_below| tree_below >= &tree).collect::<Vec<u8>>();
-    let taller_trees_left = &self.rows[y][0..=x-1].into_iter().filter(|tree_left| tree_left >= &&tree).collect::<Vec<&u8>>();
-    let taller_trees_right = &self.rows[y][x+1..].into_iter().filter(|tree_right| tree_right >= &&tree).collect::<Vec<&u8>>();
-
-    if taller_trees_above.is_empty() || taller_trees_below.is_empty() || taller_trees_left.is_empty() || taller_trees_right.is_empty() {
-      return true;
-    }
-
-    false
+  /// Whether the tree at `tree` can be seen from outside the forest, i.e. every
+  /// tree between it and one of the edges is shorter.
+  #[must_use]
+  pub fn is_tree_visible(&self, tree: Point) -> bool {
+    let height = self.trees[tree];
+    Vector::ORTHOGONAL.into_iter().any(|direction| self.trees.ray(tree, direction).all(|other| self.trees[other] < height))
   }
 
-  pub fn calculate_score_for_position(&self, x: usize, y: usize) -> u32 {
-    let mut score = 1;
-    let tree = self.rows[y][x];
-
-    if y > 0 {
-      let mut number_of_trees_above = 0;
-      let trees_above = &self.rows[..=y-1].into_iter().map(|row| row[x]).rev().collect::<Vec<u8>>();
-      for tree_above in trees_above {
-        number_of_trees_above += 1;
-        if tree_above >= &tree {
+  /// How many trees the tree at `tree` can see in each direction, multiplied
+  /// together. Trees on the edge see nothing in one direction, so score 0.
+  #[must_use]
+  pub fn calculate_score_for_position(&self, tree: Point) -> u32 {
+    let height = self.trees[tree];
+    Vector::ORTHOGONAL.into_iter().map(|direction| {
+      let mut number_of_trees = 0;
+      for other in self.trees.ray(tree, direction) {
+        number_of_trees += 1;
+        if self.trees[other] >= height {
           break;
         }
       }
-      score *= number_of_trees_above;
-    } else {
-      score *= 0;
-    }
+      number_of_trees
+    }).product()
+  }
 
-    if y < self.rows.len() - 1 {
-      let mut number_of_trees_below = 0;
-      let trees_below = &self.rows[y+1..].into_iter().map(|row| row[x]).collect::<Vec<u8>>();
-      for tree_below in trees_below {
-        number_of_trees_below += 1;
-        if tree_below >= &&tree {
-          break;
-        }
-      }
-      score *= number_of_trees_below;
-    } else {
-      score *= 0;
-    }
+  #[must_use]
+  pub fn get_best_score(&self) -> u32 {
+    self.trees.points().map(|tree| self.calculate_score_for_position(tree)).max().unwrap_or_default()
+  }
+}
 
-    if x > 0 {
-      let mut number_of_trees_left = 0;
-      let trees_left = &self.rows[y][..=x-1].into_iter().rev().collect::<Vec<&u8>>();
-      for tree_left in trees_left {
-        number_of_trees_left += 1;
-        if tree_left >= &&tree {
-          break;
-        }
-      }
-      score *= number_of_trees_left;
-    } else {
-      score *= 0;
-    }
+fn parse(reader: impl BufRead) -> Result<Forest, DayError> {
+  let trees = Grid::parse(reader, |c| c.to_digit(10).and_then(|height| u8::try_from(height).ok()).ok_or("invalid tree height"))?;
 
-    if x > 0 {
-      let mut number_of_trees_right = 0;
-      let trees_right = &self.rows[y][x+1..];
-      for tree_right in trees_right {
-        number_of_trees_right += 1;
-        if tree_right >= &&tree {
-          break;
-        }
-      }
-      score *= number_of_trees_right;
-    } else {
-      score *= 0;
-    }
+  Ok(Forest { trees })
+}
 
-    score
-  }
+pub struct Day08;
 
-  pub fn get_best_score(&self) -> u32 {
-    self.rows.iter().enumerate().fold(0, |best_score, (y, row)| {
-      let mut best_row_score = 1;
-      for (x, _tree) in row.iter().enumerate() {
-        let score = self.calculate_score_for_position(x, y);
-        if score > best_row_score {
-          best_row_score = score;
-        }
-      };
+impl Solution for Day08 {
+  const DAY: u8 = 8;
 
-      if best_row_score > best_score {
-        best_row_score
-      } else {
-        best_score
-      }
-    })
-  }
-}
+  type Input = Forest;
+  type PartOne = u32;
+  type PartTwo = u32;
 
-fn parse(filename: impl AsRef<Path>) -> Forest {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
-  let mut forest: Forest = Forest { rows: vec![], width: 0, height: 0 };
-  for line in reader.lines() {
-    let line = line.expect("could not parse line");
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
+  }
 
-    forest.width = line.len();
-    forest.height += 1;
-    forest.rows.push(line.chars().map(|c| c.to_string().parse().expect("could not parse tree height")).collect());
+  fn part_one(forest: &Self::Input) -> Result<u32, DayError> {
+    let visible = forest.trees.points().filter(|&tree| forest.is_tree_visible(tree)).count();
+    u32::try_from(visible).map_err(|_| DayError::unsolvable("too many trees to count"))
   }
 
-  forest
+  fn part_two(forest: &Self::Input) -> Result<u32, DayError> {
+    Ok(forest.get_best_score())
+  }
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> (u32, u32) {
-  let forest = parse(filename);
-  let number_of_visible_trees = forest.rows.iter().enumerate().fold(0, |trees_visible, (y, row)| {
-    let trees_visible = trees_visible + row.iter().enumerate().fold(0, |trees_visible, (x, _tree)| {
-      if forest.is_tree_visible(x, y) {
-        trees_visible + 1
-      } else {
-        trees_visible
-      }
-    });
-
-    trees_visible
-  });
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a rectangular grid of digits.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve::<Day08>(filename.as_ref())
+}
 
-  let best_score = forest.get_best_score();
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str(input: &str) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_str::<Day08>(input)
+}
 
-  (number_of_visible_trees, best_score)
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader(reader: impl BufRead) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_reader::<Day08>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
   fn calculate_score_for_position_a() {
-    let forest = parse("example.txt");
-    assert_eq!(forest.calculate_score_for_position(2, 1), 4);
+    let forest = parse(include_str!("../example.txt").as_bytes()).unwrap();
+    assert_eq!(forest.calculate_score_for_position(Point::new(2, 1)), 4);
   }
 
   #[test]
   fn calculate_score_for_position_b() {
-    let forest = parse("example.txt");
-    assert_eq!(forest.calculate_score_for_position(2, 3), 8);
+    let forest = parse(include_str!("../example.txt").as_bytes()).unwrap();
+    assert_eq!(forest.calculate_score_for_position(Point::new(2, 3)), 8);
   }
 
   #[test]
   fn calculate_score_for_position_c() {
-    let forest = parse("example.txt");
-    assert_eq!(forest.calculate_score_for_position(3, 4), 0);
+    let forest = parse(include_str!("../example.txt").as_bytes()).unwrap();
+    assert_eq!(forest.calculate_score_for_position(Point::new(3, 4)), 0);
   }
 
   #[test]
   fn calculate_score_for_position_d() {
-    let forest = parse("example.txt");
-    assert_eq!(forest.calculate_score_for_position(3, 2), 2);
+    let forest = parse(include_str!("../example.txt").as_bytes()).unwrap();
+    assert_eq!(forest.calculate_score_for_position(Point::new(3, 2)), 2);
   }
 
   #[test]
   fn calculate_score_for_position_e() {
-    let forest = parse("input.txt");
-    assert_eq!(forest.calculate_score_for_position(20, 7), 6);
+    let forest = parse(include_str!("../input.txt").as_bytes()).unwrap();
+    assert_eq!(forest.calculate_score_for_position(Point::new(20, 7)), 6);
   }
 
   #[test]
   fn calculate_score_for_position_f() {
-    let forest = parse("input.txt");
-    assert_eq!(forest.calculate_score_for_position(39, 11), 12);
+    let forest = parse(include_str!("../input.txt").as_bytes()).unwrap();
+    assert_eq!(forest.calculate_score_for_position(Point::new(39, 11)), 12);
   }
 
   #[test]
   fn calculate_score_for_position_g() {
-    let forest = parse("input.txt");
-    assert_eq!(forest.calculate_score_for_position(55, 13), 1);
+    let forest = parse(include_str!("../input.txt").as_bytes()).unwrap();
+    assert_eq!(forest.calculate_score_for_position(Point::new(55, 13)), 1);
   }
 
   #[test]
   fn calculate_score_for_position_h() {
-    let forest = parse("input.txt");
-    assert_eq!(forest.calculate_score_for_position(97, 98), 0);
+    let forest = parse(include_str!("../input.txt").as_bytes()).unwrap();
+    assert_eq!(forest.calculate_score_for_position(Point::new(97, 98)), 0);
   }
 
   #[test]
   fn solve_example() {
-    assert_eq!(solve("example.txt"), (21, 8));
+    assert_eq!(solve("example.txt").unwrap(), (21, 8));
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), (1679, 536625));
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day08>();
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn solve_malformed() {
+    let error = solve("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:3:4: invalid tree height: `x`");
+  }
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str("30373\n25512\n65332\n33549\n35390\n").unwrap(), (21, 8));
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip(rows in (1..10_usize, 1..10_usize).prop_flat_map(|(width, height)| prop::collection::vec(prop::collection::vec(0..10_u8, width), height))) {
+      let input = rows.iter().map(|row| row.iter().map(|tree| char::from(b'0' + tree)).chain(['\n']).collect::<String>()).collect::<String>();
+      prop_assert_eq!(Day08::parse(input.as_bytes())?, Forest { trees: Grid::from_rows(rows).unwrap() });
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day08::parse(input.as_bytes());
+    }
+  }
+}
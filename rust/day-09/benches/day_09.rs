@@ -0,0 +1,12 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_09::{generator, Day09};
+
+fn bench(c: &mut Criterion) {
+  // `Knot::move_to` looks for every new position in the whole history, which
+  // takes about 20 seconds a run at x10, half an hour at x100 and days at x1000;
+  // `AOC_BENCH_SCALES=10` leaves both out
+  aoc_bench::bench_day::<Day09>(c, generator::generate, aoc_bench::DEFAULT_SCALES);
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
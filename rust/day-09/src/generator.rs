@@ -0,0 +1,19 @@
+use std::fmt::Write;
+
+use aoc_runner::Rng;
+
+/// A list of rope motions roughly `scale` times the length of a real puzzle input.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  motions(&mut Rng::new(seed), 2000 * scale)
+}
+
+/// `count` motions of 1 to 10 steps each.
+pub fn motions(rng: &mut Rng, count: usize) -> String {
+  let mut input = String::new();
+  for _ in 0..count {
+    writeln!(input, "{} {}", rng.pick(&['U', 'D', 'L', 'R']), rng.range(1..=10)).unwrap();
+  }
+
+  input
+}
@@ -1,11 +1,15 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path,
 };
+use aoc_runner::{parsing, DayError, Solution};
+use grid::{Grid, Point, Vector};
+use tracing::debug;
 
-#[derive(Debug)]
+pub mod generator;
+
+#[derive(Debug, Clone, PartialEq)]
 enum Direction {
   Up,
   Down,
@@ -13,25 +17,36 @@ enum Direction {
   Right
 }
 
-#[derive(Debug)]
-struct Instruction {
+impl Direction {
+  fn step(&self) -> Vector {
+    match self {
+      Direction::Up => Vector::UP,
+      Direction::Down => Vector::DOWN,
+      Direction::Left => Vector::LEFT,
+      Direction::Right => Vector::RIGHT,
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Instruction {
   direction: Direction,
   steps: usize
 }
 
 #[derive(Debug, Clone)]
 struct Knot {
-  position: (i32, i32),
-  history: Vec<(i32, i32)>,
+  position: Point,
+  history: Vec<Point>,
   number_of_spots_visited: u32
 }
 
 impl Knot {
   fn new() -> Self {
-    Knot { position: (0, 0), history: vec![], number_of_spots_visited: 1 }
+    Knot { position: Point::ORIGIN, history: vec![], number_of_spots_visited: 1 }
   }
 
-  fn move_to(&mut self, position: (i32, i32)) {
+  fn move_to(&mut self, position: Point) {
     self.position = position;
 
     if !self.history.contains(&position) {
@@ -42,60 +57,65 @@ impl Knot {
   }
 }
 
-fn parse(filename: impl AsRef<Path>) -> Vec<Instruction> {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
+fn parse(reader: impl BufRead) -> Result<Vec<Instruction>, DayError> {
+  let directions = [("U", Direction::Up), ("D", Direction::Down), ("L", Direction::Left), ("R", Direction::Right)];
   let mut instructions = vec![];
-  for line in reader.lines() {
-    let line = line.expect("could not parse line");
+  for line in parsing::lines(reader) {
+    let line = line?;
+    let [direction, steps] = line.span().fields("expected `<direction> <steps>`")?;
+
+    instructions.push(Instruction {
+      direction: direction.one_of(&directions, "invalid direction")?,
+      steps: steps.parse("invalid steps")?,
+    });
+  }
 
-    let direction = match &line[0..1] {
-      "U" => Direction::Up,
-      "D" => Direction::Down,
-      "L" => Direction::Left,
-      "R" => Direction::Right,
-      _ => panic!("invalid direction")
-    };
+  Ok(instructions)
+}
+
+fn simulate(instructions: &[Instruction], rope_length: usize) -> u32 {
+  tail(instructions, rope_length).number_of_spots_visited
+}
 
-    let steps = line[2..].parse::<usize>().expect("invalid steps");
+/// The whole path of the last of `rope_length` knots behind the head: every
+/// position it moves to, in order, starting at the origin, a position it comes
+/// back to appearing again each time. Moving up goes towards negative `y`.
+///
+/// # Panics
+///
+/// Panics if `rope_length` is zero.
+#[must_use]
+pub fn tail_trail(instructions: &[Instruction], rope_length: usize) -> Vec<Point> {
+  [Point::ORIGIN].into_iter().chain(tail(instructions, rope_length).history).collect()
+}
 
-    instructions.push(Instruction { direction, steps });
+/// The cells of `trail` as a grid just big enough for it: 2 where it started, 1
+/// everywhere else it went and 0 everywhere else.
+#[must_use]
+pub fn trail_grid(trail: &[Point]) -> Grid<u32> {
+  let Some((mut grid, top_left)) = Grid::bounding(trail, 0) else { return Grid::default() };
+  for (&point, value) in trail.iter().zip([2].into_iter().chain([1].into_iter().cycle())) {
+    let cell = &mut grid[Point::ORIGIN + (point - top_left)];
+    *cell = (*cell).max(value);
   }
 
-  instructions
+  grid
 }
 
-fn simulate(instructions: &[Instruction], rope_length: usize) -> u32 {
+fn tail(instructions: &[Instruction], rope_length: usize) -> Knot {
   let mut knots = vec![Knot::new(); rope_length];
-  let mut head_position = (0, 0);
-
-  let get_delta = |a: (i32, i32), b: (i32, i32)| ((a.0 - b.0).abs(), (a.1 - b.1).abs());
+  let mut head_position = Point::ORIGIN;
 
   for instruction in instructions {
     for _ in 0..instruction.steps {
-      let (x, y) = match instruction.direction {
-        Direction::Up => (head_position.0, head_position.1 + 1),
-        Direction::Down => (head_position.0, head_position.1 - 1),
-        Direction::Left => (head_position.0 - 1, head_position.1),
-        Direction::Right => (head_position.0 + 1, head_position.1)
-      };
-      head_position = (x, y);
+      head_position += instruction.direction.step();
 
       let mut knot_before_position = head_position;
-      for knot in knots.iter_mut() {
-        let mut new_tail_position = (knot.position.0, knot.position.1);
-        let (delta_x, delta_y) = get_delta(knot_before_position, knot.position);
-
-        if delta_x > 1 || delta_y > 1 {
-          if delta_x >= 1 {
-            new_tail_position.0 += if knot_before_position.0 > knot.position.0 { 1 } else { -1 };
-          }
-
-          if delta_y >= 1 {
-            new_tail_position.1 += if knot_before_position.1 > knot.position.1 { 1 } else { -1 };
-          }
-
-          knot.move_to(new_tail_position);
+      for (index, knot) in (1..).zip(&mut knots) {
+        let delta = knot_before_position - knot.position;
+        if delta.chebyshev() > 1 {
+          knot.move_to(knot.position + delta.signum());
+          debug!(knot = index, x = knot.position.x, y = knot.position.y, "move");
         }
 
         knot_before_position = knot.position;
@@ -103,33 +123,121 @@ fn simulate(instructions: &[Instruction], rope_length: usize) -> u32 {
     }
   }
 
-  knots.last().expect("no tail").number_of_spots_visited
+  knots.pop().expect("no tail")
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+  const DAY: u8 = 9;
+
+  type Input = Vec<Instruction>;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
+  }
+
+  fn part_one(instructions: &Self::Input) -> Result<u32, DayError> {
+    Ok(simulate(instructions, 1))
+  }
+
+  fn part_two(instructions: &Self::Input) -> Result<u32, DayError> {
+    Ok(simulate(instructions, 9))
+  }
+}
+
+/// # Errors
+///
+/// Returns an error if the file cannot be read or has a malformed motion.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve::<Day09>(filename.as_ref())
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> (u32, u32) {
-  let instructions = parse(filename);
-  let first = simulate(&instructions, 1);
-  let second = simulate(&instructions, 9);
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str(input: &str) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_str::<Day09>(input)
+}
 
-  (first, second)
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader(reader: impl BufRead) -> Result<(u32, u32), DayError> {
+  aoc_runner::solve_reader::<Day09>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
   fn solve_example_1() {
-    assert_eq!(solve("example_1.txt"), (13, 1));
+    assert_eq!(solve("example_1.txt").unwrap(), (13, 1));
   }
 
   #[test]
   fn solve_example_2() {
-    assert_eq!(solve("example_2.txt"), (88, 36));
+    assert_eq!(solve("example_2.txt").unwrap(), (88, 36));
+  }
+
+  #[test]
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day09>();
+  }
+
+  #[test]
+  fn solve_malformed() {
+    let error = solve("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:3:1: invalid direction: `X`");
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), (6236, 2449));
+  fn tail_trail_example() {
+    let instructions = Day09::parse("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n".as_bytes()).unwrap();
+    let trail = tail_trail(&instructions, 1);
+    assert_eq!(trail[..4], [Point::new(0, 0), Point::new(1, 0), Point::new(2, 0), Point::new(3, 0)]);
+    assert_eq!(trail.iter().collect::<std::collections::HashSet<_>>().len(), 13);
+    // the tail comes back to (4, -3) on its way left, and it is there twice
+    assert_eq!(trail.len(), 14);
+    assert_eq!((trail[6], trail[10]), (Point::new(4, -3), Point::new(4, -3)));
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn trail_from_top_left() {
+    let trail = [Point::new(0, 0), Point::new(1, 0), Point::new(1, -1), Point::new(0, 0)];
+    assert_eq!(trail_grid(&trail).to_string(), "01\n21");
+    assert_eq!(trail_grid(&[]).height(), 0);
+  }
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n").unwrap(), (13, 1));
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip(motions in prop::collection::vec((prop::sample::select(vec!['U', 'D', 'L', 'R']), 0..100_usize), 0..50)) {
+      let input = motions.iter().map(|(direction, steps)| format!("{direction} {steps}")).collect::<Vec<_>>().join("\n");
+      let expected = motions.iter().map(|&(direction, steps)| {
+        let direction = match direction {
+          'U' => Direction::Up,
+          'D' => Direction::Down,
+          'L' => Direction::Left,
+          _ => Direction::Right,
+        };
+        Instruction { direction, steps }
+      }).collect::<Vec<_>>();
+      prop_assert_eq!(Day09::parse(input.as_bytes())?, expected);
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day09::parse(input.as_bytes());
+    }
+  }
+}
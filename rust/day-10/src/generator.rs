@@ -0,0 +1,24 @@
+use std::fmt::Write;
+
+use aoc_runner::Rng;
+
+/// A program roughly `scale` times the length of a real puzzle input.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  program(&mut Rng::new(seed), 140 * scale)
+}
+
+/// A program of `lines` `noop` and `addx` instructions.
+pub fn program(rng: &mut Rng, lines: usize) -> String {
+  let mut input = String::new();
+  for _ in 0..lines {
+    if rng.below(3) == 0 {
+      input.push_str("noop\n");
+    } else {
+      let sign = if rng.below(2) == 0 { "" } else { "-" };
+      writeln!(input, "addx {sign}{}", rng.range(0..=15)).unwrap();
+    }
+  }
+
+  input
+}
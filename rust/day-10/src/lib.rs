@@ -1,115 +1,170 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path,
 };
+use aoc_runner::{parsing, DayError, Solution};
+use grid::{Grid, Point};
+use tracing::debug;
 
-#[derive(Debug)]
-enum Command {
+pub mod generator;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
   Noop,
   Add(i32),
 }
 
-#[derive(Debug)]
-struct Program {
+#[derive(Debug, Clone)]
+pub struct Program {
   commands: Vec<Command>,
   output: Vec<i32>,
-  screen: Vec<Vec<char>>,
+  screen: Grid<bool>,
 }
 
 impl Program {
   fn new() -> Self {
-    Program { commands: vec![], output: vec![], screen: vec![] }
+    Program { commands: vec![], output: vec![], screen: Grid::default() }
   }
 
   pub fn add_command(&mut self, command: Command) {
     self.commands.push(command);
   }
 
-  pub fn run(&mut self) {
-    let mut screen = vec![vec![' '; 40]; 6];
+  /// Runs every command, recording the signal strength and the CRT pixel of each cycle.
+  /// Cycles past the 240 pixels of the CRT are still recorded but not drawn.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the X register or a signal strength does not fit in
+  /// an `i32`, or the program runs for more than `i32::MAX` cycles.
+  pub fn run(&mut self) -> Result<(), DayError> {
+    let mut screen = Grid::new(40, 6, false);
     let mut output = vec![1];
-    let mut cycle = 1;
-    let mut current_value = 1;
-    for command in &self.commands {
+    let mut current_value = 1_i32;
+    for (cycle, command) in (1..).zip(&self.commands) {
+      let cycle_number = i32::try_from(cycle).map_err(|_| DayError::unsolvable("program runs for more than i32::MAX cycles"))?;
       output.resize(cycle + 1, 0);
-      output[cycle] = current_value * cycle as i32;
-
-      let row = ((cycle - 1) as f64 / 40.0).floor() as usize;
-      let col = (cycle - 1 as usize) % 40;
-      if current_value > 0 {
-        if col >= (current_value - 1) as usize && col <= (current_value + 1) as usize {
-          screen[row][col] = '█';
-        }
-      } else {
-        if col <= (current_value + 1) as usize {
-          screen[row][col] = '█';
-        }
+      output[cycle] = current_value.checked_mul(cycle_number)
+        .ok_or_else(|| DayError::unsolvable(format!("signal strength at cycle {cycle} does not fit in an i32")))?;
+      debug!(cycle, x = current_value, "cycle");
+
+      // the sprite is three pixels wide, centred on X
+      let pixel = Point::new((cycle_number - 1) % 40, (cycle_number - 1) / 40);
+      if let Some(lit) = screen.get_mut(pixel) {
+        *lit = pixel.x.abs_diff(current_value) <= 1;
       }
 
       match command {
         Command::Add(value) => {
-          current_value = current_value + value;
+          current_value = current_value.checked_add(*value)
+            .ok_or_else(|| DayError::unsolvable(format!("X does not fit in an i32 after cycle {cycle}")))?;
         },
         Command::Noop => {},
       }
-      cycle += 1;
     }
 
     self.output = output;
     self.screen = screen;
+    Ok(())
   }
 
-  pub fn draw(&self) {
-    for row in &self.screen {
-      for col in row {
-        print!("{}", col);
-      }
-      println!();
+  #[must_use]
+  pub fn render(&self) -> String {
+    self.screen.render(|&lit| if lit { '█' } else { ' ' })
+  }
+
+  /// The sum of the interesting signal strengths, or `None` if the program
+  /// stopped before reaching them all.
+  #[must_use]
+  pub fn get_signal_strength(&self) -> Option<i32> {
+    let interesting_signal_strengths = [20, 60, 100, 140, 180, 220];
+    let mut first = 0;
+    for pos in interesting_signal_strengths {
+      let signal_strength = self.output.get(pos)?;
+      first += signal_strength;
     }
+
+    Some(first)
   }
 }
 
-fn parse(filename: impl AsRef<Path>) -> Program {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
+fn parse(reader: impl BufRead) -> Result<Program, DayError> {
   let mut program = Program::new();
-  for line in reader.lines() {
-    let line = line.expect("could not parse line");
+  for line in parsing::lines(reader) {
+    let line = line?;
+    let span = line.span();
+    let command = span.words().next().unwrap_or(span);
 
-    match &line[0..1] {
-      "n" => {
+    match command.as_str() {
+      "noop" => {
         program.add_command(Command::Noop);
       },
-      "a" => {
+      "addx" => {
+        let [_, value] = span.fields("expected `addx <number>`")?;
         program.add_command(Command::Noop);
-        program.add_command(Command::Add(line[5..].parse::<i32>().expect("invalid number")));
+        program.add_command(Command::Add(value.parse("invalid number")?));
       },
-      _ => panic!("invalid command"),
-    };
+      _ => return Err(command.error("invalid command")),
+    }
   }
 
-  program
+  Ok(program)
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> i32 {
-  let mut program = parse(filename);
-  program.run();
-  let interesting_signal_strengths = [20, 60, 100, 140, 180, 220];
-  let mut first = 0;
-  for pos in interesting_signal_strengths {
-    let signal_strength = program.output[pos as usize];
-    first += signal_strength;
+pub struct Day10;
+
+impl Solution for Day10 {
+  const DAY: u8 = 10;
+
+  type Input = Program;
+  type PartOne = i32;
+  type PartTwo = String;
+
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
   }
 
-  program.draw();
+  fn part_one(program: &Self::Input) -> Result<i32, DayError> {
+    let mut program = program.clone();
+    program.run()?;
+    program.get_signal_strength().ok_or_else(|| DayError::unsolvable("program stopped before cycle 220"))
+  }
+
+  fn part_two(program: &Self::Input) -> Result<String, DayError> {
+    let mut program = program.clone();
+    program.run()?;
+    Ok(program.render())
+  }
+}
+
+/// # Errors
+///
+/// Returns an error if the file cannot be read, has an invalid instruction, or
+/// stops before cycle 220.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(i32, String), DayError> {
+  aoc_runner::solve::<Day10>(filename.as_ref())
+}
+
+/// # Errors
+///
+/// Returns an error if the input has an invalid instruction or stops before cycle 220.
+pub fn solve_str(input: &str) -> Result<(i32, String), DayError> {
+  aoc_runner::solve_str::<Day10>(input)
+}
 
-  first
+/// # Errors
+///
+/// Returns an error if the input cannot be read, has an invalid instruction, or
+/// stops before cycle 220.
+pub fn solve_reader(reader: impl BufRead) -> Result<(i32, String), DayError> {
+  aoc_runner::solve_reader::<Day10>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
@@ -117,9 +172,9 @@ mod tests {
     let mut program = Program {
       commands: vec![Command::Noop, Command::Noop, Command::Add(3), Command::Noop, Command::Add(-5)],
       output: vec![],
-      screen: vec![],
+      screen: Grid::default(),
     };
-    program.run();
+    program.run().unwrap();
     assert_eq!(
       program.output,
       vec![1, 1, 2, 3, 16, 20]
@@ -128,11 +183,64 @@ mod tests {
 
   #[test]
   fn solve_example() {
-    assert_eq!(solve("example.txt"), 13140);
+    assert_eq!(solve("example.txt").unwrap().0, 13140);
+  }
+
+  #[test]
+  fn render_example() {
+    let crt = solve("example.txt").unwrap().1;
+    assert_eq!(crt.lines().next(), Some("██  ██  ██  ██  ██  ██  ██  ██  ██  ██  "));
+    assert_eq!(crt.lines().count(), 6);
+  }
+
+  #[test]
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day10>();
+  }
+
+  #[test]
+  fn solve_malformed() {
+    let error = solve("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:3:6: invalid number: `-x`");
+  }
+
+  #[test]
+  fn solve_small_program() {
+    let error = solve("small_program.txt").unwrap_err();
+    assert_eq!(error.to_string(), "program stopped before cycle 220");
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), 10760);
+  fn register_out_of_range() {
+    let error = solve_str("addx 2147483647\naddx 5\n").unwrap_err();
+    assert_eq!(error.to_string(), "X does not fit in an i32 after cycle 2");
+    let error = solve_str("addx 1073741823\nnoop\n").unwrap_err();
+    assert_eq!(error.to_string(), "signal strength at cycle 3 does not fit in an i32");
+  }
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str(include_str!("../example.txt")).unwrap().0, 13140);
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip(commands in prop::collection::vec(prop::option::of(-100..100_i32), 0..50)) {
+      let input = commands.iter().map(|command| match command {
+        Some(value) => format!("addx {value}"),
+        None => String::from("noop"),
+      }).collect::<Vec<_>>().join("\n");
+      // `addx` takes two cycles, so it is parsed as a `noop` followed by the addition
+      let expected = commands.iter().flat_map(|command| match command {
+        Some(value) => vec![Command::Noop, Command::Add(*value)],
+        None => vec![Command::Noop],
+      }).collect::<Vec<_>>();
+      prop_assert_eq!(Day10::parse(input.as_bytes())?.commands, expected);
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day10::parse(input.as_bytes());
+    }
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,10 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_11::{generator, Day11};
+
+fn bench(c: &mut Criterion) {
+  // ten thousand rounds of part two take about 13 seconds a run at x1000
+  aoc_bench::bench_day::<Day11>(c, generator::generate, aoc_bench::DEFAULT_SCALES);
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
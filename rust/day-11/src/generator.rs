@@ -0,0 +1,53 @@
+use std::fmt::Write;
+
+use aoc_runner::Rng;
+
+const MONKEYS: usize = 8;
+const DIVISORS: [u64; MONKEYS] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+/// Eight monkeys holding roughly `scale` times as many items as in a real puzzle
+/// input.
+#[must_use]
+pub fn generate(seed: u64, scale: usize) -> String {
+  monkeys(&mut Rng::new(seed), 8 * scale)
+}
+
+/// Eight monkeys holding up to `max_items` items each. The divisors are always
+/// the first eight primes, so worry levels never overflow.
+///
+/// # Panics
+///
+/// Panics if `max_items` is zero.
+pub fn monkeys(rng: &mut Rng, max_items: usize) -> String {
+  let mut divisors = DIVISORS;
+  rng.shuffle(&mut divisors);
+
+  let mut input = String::new();
+  for (monkey, divisor) in divisors.iter().enumerate() {
+    let items = (0..rng.range(1..=max_items)).map(|_| rng.range(50..=99).to_string()).collect::<Vec<_>>();
+    let operation = match rng.below(3) {
+      0 => String::from("old * old"),
+      1 => format!("old * {}", rng.range(2..=19)),
+      _ => format!("old + {}", rng.range(1..=8)),
+    };
+    let if_true = (monkey + rng.range(1..=MONKEYS - 1)) % MONKEYS;
+    let if_false = loop {
+      let target = (monkey + rng.range(1..=MONKEYS - 1)) % MONKEYS;
+      if target != if_true {
+        break target;
+      }
+    };
+
+    if monkey > 0 {
+      input.push('\n');
+    }
+    writeln!(input, "Monkey {monkey}:").unwrap();
+    writeln!(input, "  Starting items: {}", items.join(", ")).unwrap();
+    writeln!(input, "  Operation: new = {operation}").unwrap();
+    writeln!(input, "  Test: divisible by {divisor}").unwrap();
+    writeln!(input, "    If true: throw to monkey {if_true}").unwrap();
+    writeln!(input, "    If false: throw to monkey {if_false}").unwrap();
+  }
+
+  input
+}
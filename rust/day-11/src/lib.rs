@@ -1,19 +1,22 @@
 #![warn(clippy::all, clippy::pedantic)]
 use std::{
-  fs::File,
-  io::{prelude::*, BufReader},
+  io::BufRead,
   path::Path,
 };
 use core::fmt::Debug;
+use aoc_runner::{parsing, DayError, Solution};
+use tracing::{debug, debug_span};
 
-#[derive(Debug, Clone)]
+pub mod generator;
+
+#[derive(Debug, Clone, PartialEq)]
 struct MonkeyTest {
   divisible_by: u64,
   if_true: usize,
   if_false: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 enum Operation {
   Add,
   Subtract,
@@ -21,14 +24,14 @@ enum Operation {
   Divide,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct MonkeyOperation {
   operator: Operation,
   value: Option<u64>,
 }
 
-#[derive(Debug, Clone)]
-struct Monkey {
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monkey {
   items: Vec<u64>,
   operation: MonkeyOperation,
   test: MonkeyTest,
@@ -44,38 +47,43 @@ impl Monkey {
     }
   }
 
-  fn operate_on_item(&self, item: &u64) -> u64 {
-    let value = self.operation.value.unwrap_or(*item);
+  // `None` if the new worry level is not a `u64`, or is `old / old` of 0
+  fn operate_on_item(&self, item: u64) -> Option<u64> {
+    let value = self.operation.value.unwrap_or(item);
     match self.operation.operator {
-      Operation::Add => item + value,
-      Operation::Subtract => item - value,
-      Operation::Multiply => item * value,
-      Operation::Divide => item / value,
+      Operation::Add => item.checked_add(value),
+      Operation::Subtract => item.checked_sub(value),
+      Operation::Multiply => item.checked_mul(value),
+      Operation::Divide => item.checked_div(value),
     }
   }
 
   fn test_item(&self, item: u64) -> bool {
-    item % self.test.divisible_by == 0
+    item.is_multiple_of(self.test.divisible_by)
   }
 }
 
 struct MonkeyGang {
   is_calming_down: bool,
+  // every monkey's divisor multiplied together, which keeps each test's answer
+  // when worry levels are taken modulo it
+  modulus: u64,
   monkeys: Vec<Monkey>,
 }
 impl MonkeyGang {
-  fn play_round(&mut self, idx: usize) {
+  fn play_round(&mut self, idx: usize) -> Result<(), DayError> {
     let monkeys = &mut self.monkeys;
     let items = std::mem::take(&mut monkeys[idx].items);
-    let chinese_remainder_theorem_idk: u64 = monkeys.iter().map(|m| m.test.divisible_by).product();
     monkeys[idx].inspected_item_count += items.len();
 
     for item in items {
       let monkey = &mut monkeys[idx];
-      let mut new_item = monkey.operate_on_item(&item) % chinese_remainder_theorem_idk;
+      let new_item = monkey.operate_on_item(item)
+        .ok_or_else(|| DayError::unsolvable(format!("monkey {idx} cannot work out a new worry level from {item}")))?;
+      let mut new_item = new_item % self.modulus;
 
       if self.is_calming_down {
-        new_item = new_item / 3;
+        new_item /= 3;
       }
 
       let monkey_to_throw_to = if monkey.test_item(new_item) {
@@ -83,100 +91,233 @@ impl MonkeyGang {
       } else {
         monkey.test.if_false
       };
+      debug!(monkey = idx, item, worry = new_item, to = monkey_to_throw_to, "throw");
       monkeys[monkey_to_throw_to].items.push(new_item);
     }
+
+    Ok(())
   }
 }
 
-fn parse(filename: impl AsRef<Path>) -> Vec<Monkey> {
-  let file = File::open(filename).expect("file not found");
-  let reader = BufReader::new(file);
-  let all_lines = reader.lines().collect::<Vec<_>>();
-  let groups = all_lines.chunks(7);
-
+fn parse(reader: impl BufRead) -> Result<Vec<Monkey>, DayError> {
   let mut monkeys = vec![];
 
-  for group in groups {
-    let starting_items = group[1].as_ref().expect("no starting items line")
-      .trim().split(": ").nth(1).expect("no starting items")
-      .split(", ").map(|s| s.parse::<u64>().expect("not a number"))
-      .collect::<Vec<_>>();
+  for group in parsing::groups(reader) {
+    let group = group?;
+    let [header, items_line, operation_line, divisor_line, true_line, false_line] = &group[..] else {
+      match (group.get(6), group.last()) {
+        (Some(extra), _) => return Err(extra.span().error("expected a blank line after each monkey")),
+        (None, Some(last)) => return Err(last.span().error("incomplete monkey")),
+        // a blank line too many
+        (None, None) => continue,
+      }
+    };
+    header.span().keyword("Monkey")?;
+
+    let starting_items = items_line.span().trim().keyword("Starting items:")?
+      .split(", ").filter(|item| !item.is_empty()).map(|item| item.parse::<u64>("not a number"))
+      .collect::<Result<Vec<_>, _>>()?;
 
-    let operation_line = group[2].as_ref().expect("no operation line")
-        .trim().split("= ").nth(1).expect("no operation")
-        .split(" ").collect::<Vec<_>>();
+    let [operator, value] = operation_line.span().trim().keyword("Operation: new = old")?.fields("expected `<operator> <value>`")?;
+    let operator = operator.one_of(&[("+", Operation::Add), ("*", Operation::Multiply), ("-", Operation::Subtract), ("/", Operation::Divide)], "unknown operator")?;
     let operation = MonkeyOperation {
-      operator: match operation_line[1] {
-        "+" => Operation::Add,
-        "*" => Operation::Multiply,
-        "-" => Operation::Subtract,
-        "/" => Operation::Divide,
-        _ => panic!("unknown operator"),
-      },
-      value: match operation_line[2] {
+      value: match value.as_str() {
         "old" => None,
-        other => Some(other.parse::<u64>().expect("not a number")),
+        "0" if operator == Operation::Divide => return Err(value.error("division by zero")),
+        _ => Some(value.parse::<u64>("not a number")?),
       },
+      operator,
     };
 
-    let divisor_line = group[3].as_ref().expect("no divisor line")
-        .trim().split(" ").collect::<Vec<_>>();
-    let true_line = group[4].as_ref().expect("no if true line")
-        .trim().split(" ").collect::<Vec<_>>();
-    let false_line = group[5].as_ref().expect("no if false line").trim()
-        .split(" ").collect::<Vec<_>>();
+    let divisor = divisor_line.span().trim().keyword("Test: divisible by")?;
+    let divisible_by = divisor.parse::<u64>("not a number")?;
+    if divisible_by == 0 {
+      return Err(divisor.error("division by zero"));
+    }
+
     let test = MonkeyTest {
-      divisible_by: divisor_line[3].parse::<u64>().expect("not a number"),
-      if_true: true_line[5].parse::<usize>().expect("not a number"),
-      if_false: false_line[5].parse::<usize>().expect("not a number"),
+      divisible_by,
+      if_true: true_line.span().trim().keyword("If true: throw to monkey")?.parse::<usize>("not a number")?,
+      if_false: false_line.span().trim().keyword("If false: throw to monkey")?.parse::<usize>("not a number")?,
     };
 
     let monkey = Monkey::new(starting_items, operation, test);
     monkeys.push(monkey);
   }
 
-  monkeys
+  for (idx, monkey) in monkeys.iter().enumerate() {
+    for target in [monkey.test.if_true, monkey.test.if_false] {
+      if target >= monkeys.len() {
+        return Err(DayError::unsolvable(format!("monkey {idx} throws to monkey {target}, which does not exist")));
+      }
+    }
+  }
+
+  Ok(monkeys)
 }
 
-fn play(monkeys: &Vec<Monkey>, rounds: u64, should_calm_down: bool) -> Vec<Monkey> {
-  let mut gang = MonkeyGang { monkeys: monkeys.clone(), is_calming_down: should_calm_down };
+fn play(monkeys: &[Monkey], rounds: u64, should_calm_down: bool) -> Result<Vec<Monkey>, DayError> {
+  let modulus = monkeys.iter().try_fold(1_u64, |modulus, monkey| modulus.checked_mul(monkey.test.divisible_by))
+    .ok_or_else(|| DayError::unsolvable("the monkeys' divisors multiply past a u64"))?;
+  let mut gang = MonkeyGang { monkeys: monkeys.to_vec(), is_calming_down: should_calm_down, modulus };
 
-  for _ in 0..rounds {
+  for round in 1..=rounds {
+    let _round = debug_span!("round", round).entered();
     for idx in 0..monkeys.len() {
-      gang.play_round(idx);
+      gang.play_round(idx)?;
     }
   }
 
-  gang.monkeys
+  Ok(gang.monkeys)
+}
+
+fn get_monkey_business(monkeys: &[Monkey], rounds: u64, should_calm_down: bool) -> Result<usize, DayError> {
+  if monkeys.len() < 2 {
+    return Err(DayError::unsolvable("monkey business needs at least two monkeys"));
+  }
+
+  let mut monkeys = play(monkeys, rounds, should_calm_down)?;
+  monkeys.sort_by_key(|a| a.inspected_item_count);
+  monkeys.reverse();
+
+  Ok((0..2).map(|idx| monkeys[idx].inspected_item_count).product())
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+  const DAY: u8 = 11;
+
+  type Input = Vec<Monkey>;
+  type PartOne = usize;
+  type PartTwo = usize;
+
+  fn parse(reader: impl BufRead) -> Result<Self::Input, DayError> {
+    parse(reader)
+  }
+
+  fn part_one(monkeys: &Self::Input) -> Result<usize, DayError> {
+    get_monkey_business(monkeys, 20, true)
+  }
+
+  fn part_two(monkeys: &Self::Input) -> Result<usize, DayError> {
+    get_monkey_business(monkeys, 10000, false)
+  }
 }
 
-pub fn solve(filename: impl AsRef<Path>) -> (usize, usize) {
-  let monkeys = parse(filename);
-  let mut monkeys_after_20_rounds = play(&monkeys, 20, true);
-  monkeys_after_20_rounds.sort_by(|a, b| a.inspected_item_count.cmp(&b.inspected_item_count));
-  monkeys_after_20_rounds.reverse();
+/// # Errors
+///
+/// Returns an error if the file cannot be read, describes a malformed monkey,
+/// has fewer than two monkeys, or has a worry level that is not a `u64`.
+pub fn solve(filename: impl AsRef<Path>) -> Result<(usize, usize), DayError> {
+  aoc_runner::solve::<Day11>(filename.as_ref())
+}
 
-  let mut monkeys_after_10000_rounds = play(&monkeys, 10000, false);
-  monkeys_after_10000_rounds.sort_by(|a, b| a.inspected_item_count.cmp(&b.inspected_item_count));
-  monkeys_after_10000_rounds.reverse();
+/// # Errors
+///
+/// Returns an error if the input is malformed or has no answer.
+pub fn solve_str(input: &str) -> Result<(usize, usize), DayError> {
+  aoc_runner::solve_str::<Day11>(input)
+}
 
-  (
-    (0..2).map(|idx| monkeys_after_20_rounds[idx].inspected_item_count).product(),
-    (0..2).map(|idx| monkeys_after_10000_rounds[idx].inspected_item_count).product(),
-  )
+/// # Errors
+///
+/// Returns an error if the input cannot be read, is malformed, or has no answer.
+pub fn solve_reader(reader: impl BufRead) -> Result<(usize, usize), DayError> {
+  aoc_runner::solve_reader::<Day11>(reader)
 }
 
 #[cfg(test)]
 mod tests {
+  use proptest::prelude::*;
+
   use super::*;
 
   #[test]
   fn solve_example() {
-    assert_eq!(solve("example.txt"), (10605, 2713310158));
+    assert_eq!(solve("example.txt").unwrap(), (10605, 2_713_310_158));
+  }
+
+  #[test]
+  fn solve_registered_inputs() {
+    aoc_runner::registry::assert_registered::<Day11>();
   }
 
   #[test]
-  fn solve_input() {
-    assert_eq!(solve("input.txt"), (111210, 15447387620));
+  fn solve_malformed() {
+    let error = solve("malformed.txt").unwrap_err();
+    assert_eq!(error.to_string(), "malformed.txt:10:24: unknown operator: `%`");
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn solve_str_example() {
+    assert_eq!(solve_str(include_str!("../example.txt")).unwrap(), (10605, 2_713_310_158));
+  }
+
+  // a monkey on its own, which throws to itself, and another to make a pair
+  fn solve_monkey(items: &str, operation: &str, divisor: u64) -> Result<(usize, usize), DayError> {
+    solve_str(&format!(
+      "Monkey 0:\n  Starting items: {items}\n  Operation: new = old {operation}\n  Test: divisible by {divisor}\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n\n\
+       Monkey 1:\n  Starting items: 1\n  Operation: new = old + 1\n  Test: divisible by 3\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n"
+    ))
+  }
+
+  #[test]
+  fn worry_levels_out_of_range() {
+    let error = solve_monkey("3", "- 5", 7).unwrap_err();
+    assert_eq!(error.to_string(), "monkey 0 cannot work out a new worry level from 3");
+    let error = solve_monkey("0", "/ old", 7).unwrap_err();
+    assert_eq!(error.to_string(), "monkey 0 cannot work out a new worry level from 0");
+    let error = solve_monkey("4294967296", "* old", 7).unwrap_err();
+    assert_eq!(error.to_string(), "monkey 0 cannot work out a new worry level from 4294967296");
+    let error = solve_monkey("3", "+ 1", u64::MAX).unwrap_err();
+    assert_eq!(error.to_string(), "the monkeys' divisors multiply past a u64");
+  }
+
+  fn monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+    (2..6_usize).prop_flat_map(|count| {
+      let monkey = (
+        prop::collection::vec(0..100_u64, 0..5),
+        prop::sample::select(vec![Operation::Add, Operation::Subtract, Operation::Multiply, Operation::Divide]),
+        prop::option::of(1..20_u64),
+        1..20_u64,
+        0..count,
+        0..count,
+      ).prop_map(|(items, operator, value, divisible_by, if_true, if_false)| {
+        Monkey::new(items, MonkeyOperation { operator, value }, MonkeyTest { divisible_by, if_true, if_false })
+      });
+      prop::collection::vec(monkey, count)
+    })
+  }
+
+  fn render_monkeys(monkeys: &[Monkey]) -> String {
+    let monkeys = monkeys.iter().enumerate().map(|(idx, monkey)| {
+      let items = monkey.items.iter().map(u64::to_string).collect::<Vec<_>>().join(", ");
+      let operator = match monkey.operation.operator {
+        Operation::Add => '+',
+        Operation::Subtract => '-',
+        Operation::Multiply => '*',
+        Operation::Divide => '/',
+      };
+      let value = monkey.operation.value.map_or(String::from("old"), |value| value.to_string());
+      format!(
+        "Monkey {idx}:\n  Starting items: {items}\n  Operation: new = old {operator} {value}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+        monkey.test.divisible_by, monkey.test.if_true, monkey.test.if_false
+      )
+    });
+
+    monkeys.collect::<Vec<_>>().join("\n")
+  }
+
+  proptest! {
+    #[test]
+    fn parse_round_trip(monkeys in monkeys()) {
+      prop_assert_eq!(Day11::parse(render_monkeys(&monkeys).as_bytes())?, monkeys);
+    }
+
+    #[test]
+    fn parse_arbitrary_text(input in "\\PC*") {
+      let _ = Day11::parse(input.as_bytes());
+    }
+  }
+}
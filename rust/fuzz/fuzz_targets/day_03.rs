@@ -0,0 +1,8 @@
+#![no_main]
+use aoc_runner::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// any input either parses or is a `DayError`, it never panics
+fuzz_target!(|input: &[u8]| {
+  let _ = day_03::Day03::parse(input);
+});
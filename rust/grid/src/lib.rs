@@ -0,0 +1,277 @@
+#![warn(clippy::all, clippy::pedantic)]
+//! Rectangular grids of cells read from the puzzle inputs, and the points and
+//! directions to walk them with.
+mod point;
+
+use std::{
+  fmt::{self, Display},
+  io::BufRead,
+  ops::{Index, IndexMut},
+};
+
+use aoc_runner::{parsing, DayError};
+
+pub use point::{Point, Vector};
+
+/// A `width` × `height` grid stored row by row in one `Vec`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+  /// A grid with every cell set to `fill`.
+  #[must_use]
+  pub fn new(width: usize, height: usize, fill: T) -> Self
+  where
+    T: Clone,
+  {
+    Grid { width, height, cells: vec![fill; width * height] }
+  }
+
+  /// A grid just big enough for every one of `points`, with every cell set to
+  /// `fill`, and the point its top left cell stands for, or `None` if there are
+  /// no points.
+  #[must_use]
+  pub fn bounding(points: &[Point], fill: T) -> Option<(Self, Point)>
+  where
+    T: Clone,
+  {
+    let &start = points.first()?;
+    let top_left = points.iter().fold(start, |corner, point| Point::new(corner.x.min(point.x), corner.y.min(point.y)));
+    let bottom_right = points.iter().fold(start, |corner, point| Point::new(corner.x.max(point.x), corner.y.max(point.y)));
+
+    let size = bottom_right - top_left;
+    Some((Grid::new(size.x.unsigned_abs() as usize + 1, size.y.unsigned_abs() as usize + 1, fill), top_left))
+  }
+
+  /// A grid made of `rows`, or `None` if they are not all the same length.
+  pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Option<Self> {
+    let mut grid = Grid { width: 0, height: 0, cells: vec![] };
+    for row in rows {
+      if grid.height > 0 && row.len() != grid.width {
+        return None;
+      }
+      grid.width = row.len();
+      grid.height += 1;
+      grid.cells.extend(row);
+    }
+
+    Some(grid)
+  }
+
+  /// Reads one row per line, turning each character into a cell with `cell`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the input cannot be read, a row is not as long as the
+  /// first one, or `cell` rejects a character, pointing at it with what `cell`
+  /// said was wrong.
+  pub fn parse<E: Into<String>>(reader: impl BufRead, mut cell: impl FnMut(char) -> Result<T, E>) -> Result<Self, DayError> {
+    let mut grid = Grid { width: 0, height: 0, cells: vec![] };
+    for line in parsing::lines(reader) {
+      let line = line?;
+      let mut width = 0;
+      for (c, span) in line.span().chars() {
+        grid.cells.push(cell(c).map_err(|message| span.error(message))?);
+        width += 1;
+      }
+
+      if grid.height > 0 && width != grid.width {
+        return Err(line.span().error(format!("expected a row of {} cells", grid.width)));
+      }
+      grid.width = width;
+      grid.height += 1;
+    }
+
+    Ok(grid)
+  }
+
+  #[must_use]
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  #[must_use]
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  #[must_use]
+  pub fn contains(&self, point: Point) -> bool {
+    self.index(point).is_some()
+  }
+
+  #[must_use]
+  pub fn get(&self, point: Point) -> Option<&T> {
+    self.index(point).map(|index| &self.cells[index])
+  }
+
+  #[must_use]
+  pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+    self.index(point).map(|index| &mut self.cells[index])
+  }
+
+  // where `point` is in `cells`, if it is on the grid
+  fn index(&self, point: Point) -> Option<usize> {
+    let x = usize::try_from(point.x).ok().filter(|&x| x < self.width)?;
+    let y = usize::try_from(point.y).ok().filter(|&y| y < self.height)?;
+    Some(y * self.width + x)
+  }
+
+  /// Every row, top to bottom.
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    // `max(1)` so an empty grid has no rows rather than panicking
+    self.cells.chunks(self.width.max(1))
+  }
+
+  /// Every point on the grid, row by row.
+  pub fn points(&self) -> impl Iterator<Item = Point> {
+    let width = self.width;
+    (0..self.height).flat_map(move |y| (0..width).map(move |x| point_at(x, y)))
+  }
+
+  /// Every cell with its point, row by row.
+  pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+    self.points().zip(&self.cells)
+  }
+
+  /// The neighbors of `point` up, right, down and left of it that are on the grid.
+  pub fn neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+    point.neighbors().filter(|neighbor| self.contains(*neighbor))
+  }
+
+  /// The neighbors of `point` in all eight directions that are on the grid.
+  pub fn all_neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+    point.all_neighbors().filter(|neighbor| self.contains(*neighbor))
+  }
+
+  /// The points from `start` in steps of `step`, not including `start`, up to
+  /// the edge of the grid, e.g. everything a tree can see in one direction.
+  pub fn ray(&self, start: Point, step: Vector) -> impl Iterator<Item = Point> + '_ {
+    let mut point = start;
+    std::iter::from_fn(move || {
+      point += step;
+      (step != Vector::default() && self.contains(point)).then_some(point)
+    })
+  }
+
+  /// A grid of the same size with `cell` applied to every cell.
+  #[must_use]
+  pub fn map<U>(&self, mut cell: impl FnMut(Point, &T) -> U) -> Grid<U> {
+    Grid { width: self.width, height: self.height, cells: self.iter().map(|(point, value)| cell(point, value)).collect() }
+  }
+
+  /// One line per row with a character per cell, without a trailing newline.
+  #[must_use]
+  pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+    self.rows().map(|row| row.iter().map(&mut cell).collect::<String>()).collect::<Vec<_>>().join("\n")
+  }
+}
+
+// the grid is never wider or taller than its `Vec` is long, so both fit
+fn point_at(x: usize, y: usize) -> Point {
+  Point::new(i32::try_from(x).expect("grid too wide"), i32::try_from(y).expect("grid too tall"))
+}
+
+impl<T> Index<Point> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, point: Point) -> &T {
+    self.get(point).unwrap_or_else(|| panic!("{point:?} is outside the {}×{} grid", self.width, self.height))
+  }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+  fn index_mut(&mut self, point: Point) -> &mut T {
+    let (width, height) = (self.width, self.height);
+    self.get_mut(point).unwrap_or_else(|| panic!("{point:?} is outside the {width}×{height} grid"))
+  }
+}
+
+/// Each cell as it displays, one row per line.
+impl<T: Display> Display for Grid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (y, row) in self.rows().enumerate() {
+      if y > 0 {
+        writeln!(f)?;
+      }
+      for cell in row {
+        write!(f, "{cell}")?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn digits(text: &str) -> Result<Grid<u32>, DayError> {
+    Grid::parse(text.as_bytes(), |c| c.to_digit(10).ok_or("invalid digit"))
+  }
+
+  #[test]
+  fn parse_rows() {
+    let grid = digits("123\n456\n").unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Point::new(2, 1)], 6);
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.get(Point::new(0, -1)), None);
+    assert_eq!(grid, Grid::from_rows([vec![1, 2, 3], vec![4, 5, 6]]).unwrap());
+  }
+
+  #[test]
+  fn parse_errors() {
+    assert_eq!(digits("123\n4x6\n").unwrap_err().to_string(), "line 2, column 2: invalid digit: `x`");
+    assert_eq!(digits("123\n45\n").unwrap_err().to_string(), "line 2, column 1: expected a row of 3 cells: `45`");
+    assert!(Grid::from_rows([vec![1], vec![]]).is_none());
+  }
+
+  #[test]
+  fn empty() {
+    let grid = digits("").unwrap();
+    assert_eq!(grid.rows().count(), 0);
+    assert_eq!(grid.points().count(), 0);
+    assert_eq!(grid.to_string(), "");
+  }
+
+  #[test]
+  fn neighbors_on_the_grid() {
+    let grid = Grid::new(3, 3, 0);
+    assert_eq!(grid.neighbors(Point::new(1, 1)).count(), 4);
+    assert_eq!(grid.neighbors(Point::ORIGIN).collect::<Vec<_>>(), [Point::new(1, 0), Point::new(0, 1)]);
+    assert_eq!(grid.all_neighbors(Point::new(1, 1)).count(), 8);
+    assert_eq!(grid.all_neighbors(Point::new(2, 2)).count(), 3);
+  }
+
+  #[test]
+  fn rays_to_the_edge() {
+    let grid = Grid::new(4, 3, 0);
+    assert_eq!(grid.ray(Point::new(1, 1), Vector::RIGHT).collect::<Vec<_>>(), [Point::new(2, 1), Point::new(3, 1)]);
+    assert_eq!(grid.ray(Point::new(1, 1), Vector::new(-1, -1)).collect::<Vec<_>>(), [Point::ORIGIN]);
+    assert_eq!(grid.ray(Point::new(0, 1), Vector::LEFT).count(), 0);
+    assert_eq!(grid.ray(Point::new(0, 1), Vector::default()).count(), 0);
+  }
+
+  #[test]
+  fn bounding_points() {
+    let (grid, top_left) = Grid::bounding(&[Point::new(2, -1), Point::new(-1, 3), Point::new(0, 0)], 0).unwrap();
+    assert_eq!((grid.width(), grid.height(), top_left), (4, 5, Point::new(-1, -1)));
+    assert_eq!(Grid::bounding(&[Point::new(5, 5)], 0).map(|(grid, top_left)| (grid.width(), grid.height(), top_left)), Some((1, 1, Point::new(5, 5))));
+    assert!(Grid::bounding(&[], 0).is_none());
+  }
+
+  #[test]
+  fn map_and_render() {
+    let mut grid = Grid::new(3, 2, false);
+    grid[Point::new(1, 0)] = true;
+    assert_eq!(grid.render(|&lit| if lit { '#' } else { '.' }), ".#.\n...");
+    assert_eq!(grid.map(|point, _| point.x + point.y).to_string(), "012\n123");
+    assert_eq!(grid.iter().filter(|(_, lit)| **lit).map(|(point, _)| point).collect::<Vec<_>>(), [Point::new(1, 0)]);
+  }
+}
@@ -0,0 +1,170 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A position on a grid: `x` grows to the right and `y` grows downwards, so
+/// `(x, y)` is column `x` of row `y` of the input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+  pub x: i32,
+  pub y: i32,
+}
+
+/// The offset between two points, e.g. one step in a direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+  pub x: i32,
+  pub y: i32,
+}
+
+impl Point {
+  pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+  #[must_use]
+  pub const fn new(x: i32, y: i32) -> Self {
+    Point { x, y }
+  }
+
+  /// The four points next to this one, up, right, down and left.
+  pub fn neighbors(self) -> impl Iterator<Item = Point> {
+    Vector::ORTHOGONAL.into_iter().map(move |step| self + step)
+  }
+
+  /// The eight points around this one, clockwise from up.
+  pub fn all_neighbors(self) -> impl Iterator<Item = Point> {
+    Vector::ALL.into_iter().map(move |step| self + step)
+  }
+}
+
+impl Vector {
+  pub const UP: Vector = Vector { x: 0, y: -1 };
+  pub const DOWN: Vector = Vector { x: 0, y: 1 };
+  pub const LEFT: Vector = Vector { x: -1, y: 0 };
+  pub const RIGHT: Vector = Vector { x: 1, y: 0 };
+
+  /// The four directions, clockwise from up.
+  pub const ORTHOGONAL: [Vector; 4] = [Vector::UP, Vector::RIGHT, Vector::DOWN, Vector::LEFT];
+  /// The eight directions including the diagonals, clockwise from up.
+  pub const ALL: [Vector; 8] = [
+    Vector::UP,
+    Vector { x: 1, y: -1 },
+    Vector::RIGHT,
+    Vector { x: 1, y: 1 },
+    Vector::DOWN,
+    Vector { x: -1, y: 1 },
+    Vector::LEFT,
+    Vector { x: -1, y: -1 },
+  ];
+
+  #[must_use]
+  pub const fn new(x: i32, y: i32) -> Self {
+    Vector { x, y }
+  }
+
+  /// At most one step along each axis in the same direction, e.g. a knot of a
+  /// rope catching up with the one before it.
+  #[must_use]
+  pub const fn signum(self) -> Self {
+    Vector { x: self.x.signum(), y: self.y.signum() }
+  }
+
+  /// How many king moves it takes to cover, i.e. the longer of its two sides.
+  #[must_use]
+  pub const fn chebyshev(self) -> u32 {
+    let (x, y) = (self.x.unsigned_abs(), self.y.unsigned_abs());
+    if x > y { x } else { y }
+  }
+
+  /// How many steps up, down, left or right it takes to cover.
+  #[must_use]
+  pub const fn manhattan(self) -> u32 {
+    self.x.unsigned_abs() + self.y.unsigned_abs()
+  }
+}
+
+impl Add<Vector> for Point {
+  type Output = Point;
+
+  fn add(self, vector: Vector) -> Point {
+    Point { x: self.x + vector.x, y: self.y + vector.y }
+  }
+}
+
+impl AddAssign<Vector> for Point {
+  fn add_assign(&mut self, vector: Vector) {
+    *self = *self + vector;
+  }
+}
+
+impl Sub<Vector> for Point {
+  type Output = Point;
+
+  fn sub(self, vector: Vector) -> Point {
+    self + -vector
+  }
+}
+
+impl Sub for Point {
+  type Output = Vector;
+
+  fn sub(self, other: Point) -> Vector {
+    Vector { x: self.x - other.x, y: self.y - other.y }
+  }
+}
+
+impl Add for Vector {
+  type Output = Vector;
+
+  fn add(self, other: Vector) -> Vector {
+    Vector { x: self.x + other.x, y: self.y + other.y }
+  }
+}
+
+impl Neg for Vector {
+  type Output = Vector;
+
+  fn neg(self) -> Vector {
+    Vector { x: -self.x, y: -self.y }
+  }
+}
+
+impl Mul<i32> for Vector {
+  type Output = Vector;
+
+  fn mul(self, factor: i32) -> Vector {
+    Vector { x: self.x * factor, y: self.y * factor }
+  }
+}
+
+impl From<(i32, i32)> for Point {
+  fn from((x, y): (i32, i32)) -> Self {
+    Point { x, y }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn arithmetic() {
+    let point = Point::new(2, 3);
+    assert_eq!(point + Vector::UP * 3, Point::new(2, 0));
+    assert_eq!(point - Vector::LEFT, Point::new(3, 3));
+    assert_eq!(Point::new(5, 1) - point, Vector::new(3, -2));
+    assert_eq!((Point::new(5, 1) - point).signum(), Vector::new(1, -1));
+  }
+
+  #[test]
+  fn distances() {
+    assert_eq!(Vector::new(3, -5).chebyshev(), 5);
+    assert_eq!(Vector::new(3, -5).manhattan(), 8);
+    assert_eq!(Vector::ALL.iter().map(|step| step.chebyshev()).max(), Some(1));
+  }
+
+  #[test]
+  fn neighbors_clockwise_from_up() {
+    let neighbors = Point::ORIGIN.neighbors().collect::<Vec<_>>();
+    assert_eq!(neighbors, [Point::new(0, -1), Point::new(1, 0), Point::new(0, 1), Point::new(-1, 0)]);
+    assert_eq!(Point::ORIGIN.all_neighbors().count(), 8);
+    assert!(Point::ORIGIN.all_neighbors().all(|neighbor| neighbor != Point::ORIGIN));
+  }
+}